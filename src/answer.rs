@@ -0,0 +1,202 @@
+// the value a solver returns, in a form that can be stored and compared
+//
+// integers compare by value whatever type they came from, so 42usize, 42i64
+// and the text "42" in input/answers.txt are the same answer; text compares
+// with an integer by the way the integer is printed, so "42" is 42 but
+// "0123" is not 123
+
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone)]
+pub enum Answer {
+    Signed(i64),
+    Unsigned(u64),
+    Big(i128),
+    Text(String),
+    // the solver returned Err, never equal to any answer, not even the same error
+    Error(String),
+}
+
+impl Answer {
+    fn as_i128(&self) -> Option<i128> {
+        match self {
+            Answer::Signed(n) => Some(*n as i128),
+            Answer::Unsigned(n) => Some(*n as i128),
+            Answer::Big(n) => Some(*n),
+            Answer::Text(_) | Answer::Error(_) => None,
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Answer) -> bool {
+        match (self.as_i128(), other.as_i128()) {
+            (Some(a), Some(b)) => a == b,
+            (None, None) => match (self, other) {
+                (Answer::Text(a), Answer::Text(b)) => a == b,
+                _ => false,
+            },
+            (Some(n), None) => matches!(other, Answer::Text(text) if *text == n.to_string()),
+            (None, Some(n)) => matches!(self, Answer::Text(text) if *text == n.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Signed(n) => write!(f, "{}", n),
+            Answer::Unsigned(n) => write!(f, "{}", n),
+            Answer::Big(n) => write!(f, "{}", n),
+            Answer::Text(text) => write!(f, "{}", text),
+            Answer::Error(message) => write!(f, "Err({})", message),
+        }
+    }
+}
+
+// the smallest integer type that holds the number, text otherwise, also for
+// numbers that are not printed the usual way, e.g. 0123, so they are kept;
+// Ok(..) around a value is dropped, as older answer files have it
+impl FromStr for Answer {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Answer, String> {
+        let value = value.trim();
+        if value.is_empty() {
+            return Err("empty answer".to_string());
+        }
+        if let Some(inner) = value.strip_prefix("Ok(").and_then(|v| v.strip_suffix(')')) {
+            return inner.parse();
+        }
+        if let Some(inner) = value.strip_prefix("Err(").and_then(|v| v.strip_suffix(')')) {
+            return Ok(Answer::Error(inner.to_string()));
+        }
+        let canonical = |n: &dyn ToString| n.to_string() == value;
+        if let Some(n) = value.parse::<i64>().ok().filter(|n| canonical(n)) {
+            Ok(Answer::Signed(n))
+        } else if let Some(n) = value.parse::<u64>().ok().filter(|n| canonical(n)) {
+            Ok(Answer::Unsigned(n))
+        } else if let Some(n) = value.parse::<i128>().ok().filter(|n| canonical(n)) {
+            Ok(Answer::Big(n))
+        } else {
+            Ok(Answer::Text(value.to_string()))
+        }
+    }
+}
+
+macro_rules! from_signed {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(n: $t) -> Answer {
+                Answer::Signed(n as i64)
+            }
+        })*
+    };
+}
+
+macro_rules! from_unsigned {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(n: $t) -> Answer {
+                Answer::Unsigned(n as u64)
+            }
+        })*
+    };
+}
+
+from_signed!(i8, i16, i32, i64, isize);
+from_unsigned!(u8, u16, u32, u64, usize);
+
+impl From<i128> for Answer {
+    fn from(n: i128) -> Answer {
+        Answer::Big(n)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Answer {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Answer {
+        Answer::Text(text.to_string())
+    }
+}
+
+impl<T: Into<Answer>, E: fmt::Debug> From<Result<T, E>> for Answer {
+    fn from(result: Result<T, E>) -> Answer {
+        match result {
+            Ok(value) => value.into(),
+            Err(e) => Answer::Error(format!("{:?}", e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_equal_across_types() {
+        assert_eq!(Answer::from(42usize), Answer::from(42i64));
+        assert_eq!(
+            Answer::from(Ok::<i64, String>(131376)),
+            "131376".parse().unwrap()
+        );
+        assert_eq!(Answer::from(7i128), Answer::from(7u8));
+        assert_ne!(Answer::from(42), Answer::from("42x"));
+        assert_eq!(Answer::from("42".to_string()), Answer::from(42));
+        assert_ne!(Answer::from("0123"), Answer::from(123));
+        assert_ne!(Answer::from(Err::<i64, &str>("no")), Answer::from("no"));
+        assert_ne!(
+            Answer::from(Err::<i64, &str>("no")),
+            Answer::from(Err::<i64, &str>("no"))
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Answer::from(-3).to_string(), "-3");
+        assert_eq!(Answer::from(u64::MAX).to_string(), "18446744073709551615");
+        assert_eq!(Answer::from("EZPJ").to_string(), "EZPJ");
+        assert_eq!(
+            Answer::from(Err::<i64, &str>("too short")).to_string(),
+            "Err(\"too short\")"
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("42".parse::<Answer>(), Ok(Answer::Signed(42)));
+        assert!(matches!(
+            "18446744073709551615".parse(),
+            Ok(Answer::Unsigned(u64::MAX))
+        ));
+        assert!(matches!(
+            "-18446744073709551616".parse(),
+            Ok(Answer::Big(_))
+        ));
+        assert!(matches!("Ok(35)".parse(), Ok(Answer::Signed(35))));
+        assert!(matches!(" EZPJ ".parse(), Ok(Answer::Text(text)) if text == "EZPJ"));
+        assert!(matches!("0123".parse(), Ok(Answer::Text(text)) if text == "0123"));
+        assert_eq!("".parse::<Answer>(), Err("empty answer".to_string()));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for answer in [
+            Answer::from(-5),
+            Answer::from(i128::MAX),
+            Answer::from("abc"),
+            Answer::from("42".to_string()),
+            Answer::from("0123"),
+        ] {
+            assert_eq!(answer.to_string().parse::<Answer>(), Ok(answer));
+        }
+        let error = Answer::from(Err::<u8, u8>(1)).to_string().parse();
+        assert!(matches!(error, Ok(Answer::Error(message)) if message == "1"));
+    }
+}
@@ -0,0 +1,232 @@
+// the expected answers, stored in input/answers.txt and for every user in
+// input/<user>/answers.txt
+//
+// the file has one section per input file and one line per part:
+//
+//   [day1_test]
+//   1 = 142
+//
+// values are answers as printed by src/answer.rs
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::answer::Answer;
+use crate::input::split_user;
+use crate::read_optional;
+
+// answers.txt in the input directory or in the one of the user
+pub fn answers_file(user: Option<&str>) -> PathBuf {
+    let dir = crate::input::input_dir();
+    match user {
+        Some(user) => dir.join(user).join("answers.txt"),
+        None => dir.join("answers.txt"),
+    }
+}
+
+const HEADER: &str = "# expected answers per input file and part, see src/answers.rs\n";
+
+// serializes load, change and save while recording
+static LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, PartialEq)]
+pub struct Answers {
+    path: PathBuf,
+    // sections in file order, each with its parts in file order
+    sections: Vec<(String, Vec<(String, String)>)>,
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Answers, String> {
+        let content = read_optional(path)?;
+        let mut answers =
+            Answers::parse(&content).map_err(|e| format!("{}: {}", path.display(), e))?;
+        answers.path = path.to_path_buf();
+        Ok(answers)
+    }
+
+    fn parse(content: &str) -> Result<Answers, String> {
+        let mut answers = Answers {
+            path: PathBuf::new(),
+            sections: Vec::new(),
+        };
+
+        for (line_no, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if line.starts_with('[') && line.ends_with(']') {
+                let input = &line[1..line.len() - 1];
+                answers.sections.push((input.to_string(), Vec::new()));
+            } else {
+                let (part, value) = line
+                    .split_once('=')
+                    .ok_or(format!("line {}: expected PART = VALUE", line_no + 1))?;
+                let section = answers.sections.last_mut().ok_or(format!(
+                    "line {}: answer outside of a [section]",
+                    line_no + 1
+                ))?;
+                section
+                    .1
+                    .push((part.trim().to_string(), value.trim().to_string()));
+            }
+        }
+
+        Ok(answers)
+    }
+
+    pub fn get(&self, input_file: &str, part: &str) -> Option<&str> {
+        self.sections
+            .iter()
+            .find(|(input, _)| input == input_file)
+            .and_then(|(_, parts)| parts.iter().find(|(p, _)| p == part))
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn set(&mut self, input_file: &str, part: &str, value: &str) {
+        let index = match self
+            .sections
+            .iter()
+            .position(|(input, _)| input == input_file)
+        {
+            Some(index) => index,
+            None => {
+                self.sections.push((input_file.to_string(), Vec::new()));
+                self.sections.len() - 1
+            }
+        };
+        let parts = &mut self.sections[index].1;
+        match parts.iter_mut().find(|(p, _)| p == part) {
+            Some(entry) => entry.1 = value.to_string(),
+            None => parts.push((part.to_string(), value.to_string())),
+        }
+    }
+
+    // written under another name first and then renamed, so a day that reads
+    // the answers while another one records never sees half a file
+    pub fn save(&self) -> std::io::Result<()> {
+        let mut content = String::from(HEADER);
+        for (input, parts) in &self.sections {
+            content.push('\n');
+            content.push_str(&format!("[{}]\n", input));
+            for (part, value) in parts {
+                content.push_str(&format!("{} = {}\n", part, value));
+            }
+        }
+        let partial = self.path.with_extension("txt.part");
+        std::fs::write(&partial, content).and_then(|_| std::fs::rename(&partial, &self.path))
+    }
+}
+
+// input files of a user, e.g. alice/day7, use the answers of that user
+pub fn expected(input_file: &str, part: &str) -> Result<Option<Answer>, String> {
+    let (user, input_file) = split_user(input_file);
+    let path = answers_file(user);
+    let answers = Answers::load(&path)?;
+    answers
+        .get(input_file, part)
+        .map(|value| value.parse::<Answer>())
+        .transpose()
+        .map_err(|e| format!("{}: [{}] {}: {}", path.display(), input_file, part, e))
+}
+
+// an error is never recorded, it would not match itself anyway
+pub fn record(input_file: &str, part: &str, value: &Answer) -> Result<(), String> {
+    if let Answer::Error(_) = value {
+        return Err("an error is not recorded as the answer".to_string());
+    }
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let (user, input_file) = split_user(input_file);
+    let mut answers = Answers::load(&answers_file(user))?;
+    if answers.get(input_file, part).is_none() {
+        answers.set(input_file, part, &value.to_string());
+        answers
+            .save()
+            .map_err(|e| format!("Could not write {}: {}", answers.path.display(), e))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::temp_path;
+
+    #[test]
+    fn test_parse() {
+        let answers =
+            Answers::parse("# comment\n[day1]\n1 = 55130\n2 = 54985\n\n[day6_test]\n1 = Ok(288)\n")
+                .unwrap();
+        assert_eq!(answers.get("day1", "2"), Some("54985"));
+        assert_eq!(answers.get("day6_test", "1"), Some("Ok(288)"));
+        assert_eq!(answers.get("day6_test", "2"), None);
+        assert_eq!(answers.get("day7", "1"), None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Answers::parse("1 = 2").unwrap_err(),
+            "line 1: answer outside of a [section]"
+        );
+        assert_eq!(
+            Answers::parse("[day1]\n1 2").unwrap_err(),
+            "line 2: expected PART = VALUE"
+        );
+    }
+
+    #[test]
+    fn test_record_refuses_errors() {
+        let error = Answer::Error("\"too short\"".to_string());
+        assert_eq!(
+            record("day1", "1", &error),
+            Err("an error is not recorded as the answer".to_string())
+        );
+    }
+
+    #[test]
+    fn test_answers_file() {
+        assert!(answers_file(None).ends_with("input/answers.txt"));
+        assert!(answers_file(Some("alice")).ends_with("input/alice/answers.txt"));
+    }
+
+    #[test]
+    fn test_set_and_save() {
+        let path = temp_path("answers.txt");
+        let mut answers = Answers::load(&path).unwrap();
+        answers.set("day1", "1", "142");
+        answers.set("day1", "2", "281");
+        answers.set("day2", "1", "8");
+        answers.set("day1", "1", "143");
+        answers.save().unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            content,
+            HEADER.to_string() + "\n[day1]\n1 = 143\n2 = 281\n\n[day2]\n1 = 8\n"
+        );
+        assert!(!path.with_extension("txt.part").exists());
+    }
+
+    #[test]
+    fn test_load_while_saving() {
+        let path = temp_path("answers.txt");
+        let mut answers = Answers::load(&path).unwrap();
+        answers.set("day1", "1", "142");
+        answers.save().unwrap();
+
+        let writer = std::thread::spawn(move || {
+            for _ in 0..200 {
+                answers.save().unwrap();
+            }
+        });
+        for _ in 0..200 {
+            let loaded = Answers::load(&path).unwrap();
+            assert_eq!(loaded.get("day1", "1"), Some("142"));
+        }
+        writer.join().unwrap();
+        std::fs::remove_file(&path).unwrap();
+    }
+}
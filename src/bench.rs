@@ -0,0 +1,176 @@
+// repeated measurement of a function with simple statistics
+
+use std::fmt;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone)]
+pub struct BenchConfig {
+    // how long the function is run before measuring starts
+    pub warmup: Duration,
+    // how many measurements are taken at most
+    pub samples: usize,
+    // measuring stops after this time even if not all samples are taken
+    pub max_time: Duration,
+    // how long the first run may take, a function that takes longer is not measured
+    pub timeout: Option<Duration>,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        BenchConfig {
+            warmup: Duration::from_millis(200),
+            samples: 100,
+            max_time: Duration::from_secs(5),
+            timeout: None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Stats {
+    pub samples: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+    // samples outside of the tukey fences (1.5 times the interquartile range)
+    pub outliers: usize,
+}
+
+// runs `routine` on fresh values from `setup` until the configured number of
+// samples or the maximum time is reached, only `routine` is measured
+pub fn measure<I, O>(
+    config: &BenchConfig,
+    mut setup: impl FnMut() -> I,
+    mut routine: impl FnMut(I) -> O,
+) -> Stats {
+    let start = Instant::now();
+    while start.elapsed() < config.warmup {
+        black_box(routine(black_box(setup())));
+    }
+
+    let mut samples = Vec::with_capacity(config.samples);
+    let start = Instant::now();
+    while samples.len() < config.samples.max(1)
+        && (samples.is_empty() || start.elapsed() < config.max_time)
+    {
+        let input = setup();
+        let sample_start = Instant::now();
+        black_box(routine(black_box(input)));
+        samples.push(sample_start.elapsed());
+    }
+
+    stats(&mut samples)
+}
+
+// value at the given fraction of the sorted samples, interpolated linearly
+fn percentile(sorted: &[Duration], fraction: f64) -> Duration {
+    let pos = fraction * (sorted.len() - 1) as f64;
+    let lower = pos.floor() as usize;
+    let upper = pos.ceil() as usize;
+    let weight = pos - lower as f64;
+    sorted[lower].mul_f64(1.0 - weight) + sorted[upper].mul_f64(weight)
+}
+
+pub fn stats(samples: &mut [Duration]) -> Stats {
+    assert!(!samples.is_empty(), "no samples to calculate stats from");
+    samples.sort();
+
+    let q1 = percentile(samples, 0.25).as_secs_f64();
+    let q3 = percentile(samples, 0.75).as_secs_f64();
+    let iqr = q3 - q1;
+    let outliers = samples
+        .iter()
+        .map(|s| s.as_secs_f64())
+        .filter(|s| *s < q1 - 1.5 * iqr || *s > q3 + 1.5 * iqr)
+        .count();
+
+    Stats {
+        samples: samples.len(),
+        min: samples[0],
+        median: percentile(samples, 0.5),
+        mean: samples.iter().sum::<Duration>() / samples.len() as u32,
+        p95: percentile(samples, 0.95),
+        outliers,
+    }
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+    if nanos < 1_000 {
+        format!("{}ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.2}µs", nanos as f64 / 1e3)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.2}ms", nanos as f64 / 1e6)
+    } else {
+        format!("{:.2}s", nanos as f64 / 1e9)
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "min {}, median {}, mean {}, p95 {} ({} samples, {} outliers)",
+            format_duration(self.min),
+            format_duration(self.median),
+            format_duration(self.mean),
+            format_duration(self.p95),
+            self.samples,
+            self.outliers
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|m| Duration::from_millis(*m)).collect()
+    }
+
+    #[test]
+    fn test_stats() {
+        let mut samples = millis(&[5, 1, 4, 2, 3]);
+        let stats = stats(&mut samples);
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.mean, Duration::from_millis(3));
+        assert_eq!(stats.p95, Duration::from_micros(4800));
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn test_stats_outliers() {
+        let mut samples = millis(&[10, 11, 10, 12, 11, 10, 11, 90]);
+        let stats = stats(&mut samples);
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.median, Duration::from_millis(11));
+    }
+
+    #[test]
+    fn test_measure_takes_samples() {
+        let config = BenchConfig {
+            warmup: Duration::ZERO,
+            samples: 10,
+            max_time: Duration::from_secs(1),
+            timeout: None,
+        };
+        let mut setups = 0;
+        let stats = measure(&config, || setups += 1, |_| ());
+        assert_eq!(stats.samples, 10);
+        assert_eq!(setups, 10);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_nanos(512)), "512ns");
+        assert_eq!(format_duration(Duration::from_nanos(1_500)), "1.50µs");
+        assert_eq!(format_duration(Duration::from_micros(2_250)), "2.25ms");
+        assert_eq!(format_duration(Duration::from_millis(3_000)), "3.00s");
+    }
+}
@@ -0,0 +1,208 @@
+// answers of earlier runs, so days whose input and solver did not change
+// need not be solved again
+//
+// the cache is .cache.tsv next to the input directory, one line per day,
+// solver version, input and part:
+//
+//   DAY  VERSION  INPUT_HASH  PART  KIND  ANSWER
+//
+// the version is Day::VERSION, bumping it makes the runner solve the day again,
+// the kind keeps the variant of the answer, so text that looks like a number
+// comes back as text
+
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::answer::Answer;
+use crate::input;
+use crate::read_optional;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mode {
+    Off,
+    // answers are taken from the cache when there are any, new ones are stored
+    Use,
+    // every day is solved again and its answers replace the cached ones
+    Refresh,
+}
+
+// serializes load, change and save
+static LOCK: Mutex<()> = Mutex::new(());
+
+pub fn cache_file() -> PathBuf {
+    input::input_dir().with_file_name(".cache.tsv")
+}
+
+// FNV-1a, unlike the hasher of std it gives the same hash in every build
+pub fn hash(content: &str) -> u64 {
+    content.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Key {
+    pub day: String,
+    pub version: String,
+    pub input_hash: u64,
+    pub part: String,
+}
+
+#[derive(Debug, Default)]
+pub struct Cache {
+    entries: Vec<(Key, Answer)>,
+}
+
+fn encode(answer: &Answer) -> (&'static str, String) {
+    match answer {
+        Answer::Signed(n) => ("signed", n.to_string()),
+        Answer::Unsigned(n) => ("unsigned", n.to_string()),
+        Answer::Big(n) => ("big", n.to_string()),
+        Answer::Text(text) => ("text", text.clone()),
+        Answer::Error(message) => ("error", message.clone()),
+    }
+}
+
+fn decode(kind: &str, value: &str) -> Option<Answer> {
+    match kind {
+        "signed" => value.parse().ok().map(Answer::Signed),
+        "unsigned" => value.parse().ok().map(Answer::Unsigned),
+        "big" => value.parse().ok().map(Answer::Big),
+        "text" => Some(Answer::Text(value.to_string())),
+        "error" => Some(Answer::Error(value.to_string())),
+        _ => None,
+    }
+}
+
+impl Cache {
+    pub fn load(path: &Path) -> Result<Cache, String> {
+        Ok(Cache::parse(&read_optional(path)?))
+    }
+
+    // broken lines are dropped
+    pub fn parse(content: &str) -> Cache {
+        let entries = content
+            .lines()
+            .filter_map(
+                |line| match line.split('\t').collect::<Vec<&str>>().as_slice() {
+                    [day, version, input_hash, part, kind, answer] => Some((
+                        Key {
+                            day: day.to_string(),
+                            version: version.to_string(),
+                            input_hash: u64::from_str_radix(input_hash, 16).ok()?,
+                            part: part.to_string(),
+                        },
+                        decode(kind, answer)?,
+                    )),
+                    _ => None,
+                },
+            )
+            .collect();
+        Cache { entries }
+    }
+
+    pub fn get(&self, key: &Key) -> Option<Answer> {
+        let (_, answer) = self.entries.iter().find(|(k, _)| k == key)?;
+        Some(answer.clone())
+    }
+
+    // replaces the answer of an older version for the same input, answers
+    // that do not fit on one line are not cached
+    pub fn set(&mut self, key: Key, answer: &Answer) {
+        if encode(answer).1.contains(['\t', '\n', '\r']) {
+            return;
+        }
+        self.entries.retain(|(k, _)| {
+            !(k.day == key.day && k.input_hash == key.input_hash && k.part == key.part)
+        });
+        self.entries.push((key, answer.clone()));
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for (key, answer) in &self.entries {
+            let (kind, answer) = encode(answer);
+            let _ = writeln!(
+                text,
+                "{}\t{}\t{:016x}\t{}\t{}\t{}",
+                key.day, key.version, key.input_hash, key.part, kind, answer
+            );
+        }
+        text
+    }
+}
+
+pub fn lookup(key: &Key) -> Option<Answer> {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    Cache::load(&cache_file()).ok()?.get(key)
+}
+
+pub fn store(entries: Vec<(Key, Answer)>) -> Result<(), String> {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let path = cache_file();
+    let mut cache = Cache::load(&path)?;
+    for (key, answer) in entries {
+        cache.set(key, &answer);
+    }
+    std::fs::write(&path, cache.to_text())
+        .map_err(|e| format!("Could not write {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(version: &str, input_hash: u64, part: &str) -> Key {
+        Key {
+            day: "day7".to_string(),
+            version: version.to_string(),
+            input_hash,
+            part: part.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_hash() {
+        assert_eq!(hash(""), 0xcbf29ce484222325);
+        assert_eq!(hash("a"), 0xaf63dc4c8601ec8c);
+        assert_ne!(hash("32T3K 765\n"), hash("32T3K 766\n"));
+    }
+
+    #[test]
+    fn test_get_and_set() {
+        let mut cache = Cache::default();
+        cache.set(key("1", 42, "1"), &Answer::from(6440));
+        cache.set(key("1", 42, "2"), &Answer::from("EZPJ"));
+        assert_eq!(cache.get(&key("1", 42, "1")), Some(Answer::from(6440)));
+        assert_eq!(cache.get(&key("2", 42, "1")), None);
+        assert_eq!(cache.get(&key("1", 43, "1")), None);
+
+        cache.set(key("2", 42, "1"), &Answer::from(5905));
+        assert_eq!(cache.get(&key("1", 42, "1")), None);
+        assert_eq!(cache.get(&key("2", 42, "1")), Some(Answer::from(5905)));
+
+        cache.set(key("2", 42, "2"), &Answer::from("two\nlines"));
+        assert_eq!(cache.get(&key("2", 42, "2")), None);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut cache = Cache::default();
+        cache.set(key("1", 42, "1"), &Answer::from(-3));
+        cache.set(key("1", 7, "2"), &Answer::from(Err::<u8, &str>("no path")));
+        cache.set(key("1", 7, "1"), &Answer::from("Ok(5)"));
+        let text = cache.to_text();
+        assert_eq!(
+            text.lines().next(),
+            Some("day7\t1\t000000000000002a\t1\tsigned\t-3")
+        );
+        let parsed = Cache::parse(&(text.clone() + "broken\nday7\t1\t2a\t1\t-3\n"));
+        assert_eq!(parsed.to_text(), text);
+        // text that looks like an answer of another kind stays text
+        assert!(matches!(
+            parsed.get(&key("1", 7, "1")),
+            Some(Answer::Text(text)) if text == "Ok(5)"
+        ));
+    }
+}
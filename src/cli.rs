@@ -0,0 +1,824 @@
+// command line parsing for the runner binary
+
+use std::time::Duration;
+
+use crate::cache::Mode;
+use crate::input::Source;
+
+pub const USAGE: &str = "usage: advent_of_code_2023 [run] <all | DAY | FROM..TO | FROM..=TO> [--part 1|2] [--record] [--format text|json|junit] [--timeout SECONDS] [--input PATH|-] [--user NAME | --all-users] [--normalize] [--cache | --force] [--jobs N]
+       advent_of_code_2023 bench <all | DAY | FROM..TO | FROM..=TO> [--part 1|2] [--samples N] [--timeout SECONDS]
+       advent_of_code_2023 watch DAY [--part 1|2] [--timeout SECONDS] [--rebuild]
+       advent_of_code_2023 new DAY
+       advent_of_code_2023 fetch DAY
+       advent_of_code_2023 submit DAY 1|2
+       advent_of_code_2023 readme
+       advent_of_code_2023 compare [--baseline COMMIT] [--threshold PERCENT]";
+
+#[derive(Debug, PartialEq)]
+pub enum Days {
+    All,
+    Single(u32),
+    Range(u32, u32),
+}
+
+impl Days {
+    // returns the days out of `available` that are selected
+    pub fn select(&self, available: &[u32]) -> Result<Vec<u32>, String> {
+        match self {
+            Days::All => Ok(available.to_vec()),
+            Days::Single(day) => {
+                if available.contains(day) {
+                    Ok(vec![*day])
+                } else {
+                    Err(format!("day {} is not implemented", day))
+                }
+            }
+            Days::Range(from, to) => {
+                let days: Vec<u32> = available
+                    .iter()
+                    .filter(|day| *day >= from && *day <= to)
+                    .cloned()
+                    .collect();
+                if days.is_empty() {
+                    Err(format!("no day between {} and {} is implemented", from, to))
+                } else {
+                    Ok(days)
+                }
+            }
+        }
+    }
+}
+
+// whose input files are used, see src/input.rs
+#[derive(Debug, Clone, PartialEq)]
+pub enum Users {
+    // the input files directly in input/
+    Shared,
+    Named(String),
+    // the shared input files and the ones of every user
+    All,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+    Junit,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run {
+        days: Days,
+        part: Option<u8>,
+        // store results for which no answer is known yet
+        record: bool,
+        format: Format,
+        timeout: Option<Duration>,
+        // read the input of the selected day from a file or stdin
+        input: Option<Source>,
+        users: Users,
+        // remove what the input lint warns about before solving
+        normalize: bool,
+        // whether answers are taken from and stored in the result cache
+        cache: Mode,
+        // how many days run at the same time
+        jobs: Option<usize>,
+    },
+    Bench {
+        days: Days,
+        part: Option<u8>,
+        samples: Option<usize>,
+        timeout: Option<Duration>,
+    },
+    // reruns a day whenever its input files change, see src/watch.rs
+    Watch {
+        day: u32,
+        part: Option<u8>,
+        timeout: Option<Duration>,
+        // also rebuild and restart when the source file of the day changes
+        rebuild: bool,
+    },
+    // creates and registers the module and input files of a day, see src/scaffold.rs
+    New {
+        day: u32,
+    },
+    // downloads the puzzle input of a day, see src/client.rs
+    Fetch {
+        day: u32,
+    },
+    // solves a part and submits the answer, see src/submit.rs
+    Submit {
+        day: u32,
+        part: u8,
+    },
+    // runs all days and writes the results table into README.md
+    Readme,
+    // compares the solve times of the latest run with an earlier one, see src/history.rs
+    Compare {
+        baseline: Option<String>,
+        threshold: Option<f64>,
+    },
+}
+
+pub fn parse_args(args: &[String]) -> Result<Command, String> {
+    let mut args = args.iter().map(|s| s.as_str()).peekable();
+    if args.peek() == Some(&"readme") {
+        args.next();
+        return match args.next() {
+            Some(arg) => Err(format!("unexpected argument {}", arg)),
+            None => Ok(Command::Readme),
+        };
+    }
+    if args.peek() == Some(&"new") {
+        args.next();
+        let day = parse_day(args.next().ok_or("new needs a day")?)?;
+        return match args.next() {
+            Some(arg) => Err(format!("unexpected argument {}", arg)),
+            None => Ok(Command::New { day }),
+        };
+    }
+    if args.peek() == Some(&"fetch") {
+        args.next();
+        let day = parse_day(args.next().ok_or("fetch needs a day")?)?;
+        return match args.next() {
+            Some(arg) => Err(format!("unexpected argument {}", arg)),
+            None => Ok(Command::Fetch { day }),
+        };
+    }
+    if args.peek() == Some(&"submit") {
+        args.next();
+        let day = parse_day(args.next().ok_or("submit needs a day and a part")?)?;
+        let part = parse_part(args.next().ok_or("submit needs a day and a part")?)?;
+        return match args.next() {
+            Some(arg) => Err(format!("unexpected argument {}", arg)),
+            None => Ok(Command::Submit { day, part }),
+        };
+    }
+    if args.peek() == Some(&"compare") {
+        args.next();
+        let mut baseline = None;
+        let mut threshold = None;
+        while let Some(arg) = args.next() {
+            match arg {
+                "--baseline" => {
+                    let value = args.next().ok_or("--baseline needs a commit")?;
+                    baseline = Some(value.to_string());
+                }
+                "--threshold" => {
+                    let value = args.next().ok_or("--threshold needs a value")?;
+                    threshold = Some(parse_threshold(value)?);
+                }
+                _ if arg.starts_with('-') => return Err(format!("unknown option {}", arg)),
+                _ => return Err(format!("unexpected argument {}", arg)),
+            }
+        }
+        return Ok(Command::Compare {
+            baseline,
+            threshold,
+        });
+    }
+    let command = match args.peek() {
+        Some(&"run") | Some(&"bench") | Some(&"watch") => args.next().unwrap(),
+        _ => "run",
+    };
+
+    let mut days = None;
+    let mut part = None;
+    let mut record = false;
+    let mut format = Format::Text;
+    let mut samples = None;
+    let mut timeout = None;
+    let mut input = None;
+    let mut users = Users::Shared;
+    let mut normalize = false;
+    let mut cache = Mode::Off;
+    let mut jobs = None;
+    let mut rebuild = false;
+    while let Some(arg) = args.next() {
+        match arg {
+            "--part" | "-p" => {
+                let value = args.next().ok_or("--part needs a value")?;
+                part = Some(parse_part(value)?);
+            }
+            "--record" if command == "run" => record = true,
+            "--format" if command == "run" => {
+                let value = args.next().ok_or("--format needs a value")?;
+                format = parse_format(value)?;
+            }
+            "--samples" if command == "bench" => {
+                let value = args.next().ok_or("--samples needs a value")?;
+                let value = value
+                    .parse::<usize>()
+                    .map_err(|_| format!("{} is not a number of samples", value))?;
+                samples = Some(value);
+            }
+            "--input" if command == "run" => {
+                let value = args.next().ok_or("--input needs a path or -")?;
+                input = Some(Source::parse(value));
+            }
+            "--user" if command == "run" => {
+                let value = args.next().ok_or("--user needs a name")?;
+                users = Users::Named(parse_user(value)?);
+            }
+            "--all-users" if command == "run" => users = Users::All,
+            "--normalize" if command == "run" => normalize = true,
+            "--cache" if command == "run" => {
+                if cache == Mode::Off {
+                    cache = Mode::Use;
+                }
+            }
+            // solves everything again, the cache gets the new answers
+            "--force" if command == "run" => cache = Mode::Refresh,
+            "--jobs" | "-j" if command == "run" => {
+                let value = args.next().ok_or("--jobs needs a value")?;
+                jobs = Some(parse_jobs(value)?);
+            }
+            "--rebuild" if command == "watch" => rebuild = true,
+            "--timeout" => {
+                let value = args.next().ok_or("--timeout needs a value")?;
+                timeout = Some(parse_timeout(value)?);
+            }
+            _ if arg.starts_with('-') => return Err(format!("unknown option {}", arg)),
+            _ if days.is_none() => days = Some(parse_days(arg)?),
+            _ => return Err(format!("unexpected argument {}", arg)),
+        }
+    }
+    let days = days.unwrap_or(Days::All);
+    if input.is_some() && !matches!(days, Days::Single(_)) {
+        return Err("--input needs a single day".to_string());
+    }
+    if input.is_some() && users != Users::Shared {
+        return Err("--input can not be combined with --user or --all-users".to_string());
+    }
+
+    if command == "watch" {
+        match days {
+            Days::Single(day) => Ok(Command::Watch {
+                day,
+                part,
+                timeout,
+                rebuild,
+            }),
+            _ => Err("watch needs a single day".to_string()),
+        }
+    } else if command == "bench" {
+        Ok(Command::Bench {
+            days,
+            part,
+            samples,
+            timeout,
+        })
+    } else {
+        Ok(Command::Run {
+            days,
+            part,
+            record,
+            format,
+            timeout,
+            input,
+            users,
+            normalize,
+            cache,
+            jobs,
+        })
+    }
+}
+
+fn parse_part(value: &str) -> Result<u8, String> {
+    match value {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => Err(format!("unknown part {}, expected 1 or 2", value)),
+    }
+}
+
+fn parse_format(value: &str) -> Result<Format, String> {
+    match value {
+        "text" => Ok(Format::Text),
+        "json" => Ok(Format::Json),
+        "junit" => Ok(Format::Junit),
+        _ => Err(format!(
+            "unknown format {}, expected text, json or junit",
+            value
+        )),
+    }
+}
+
+fn parse_user(value: &str) -> Result<String, String> {
+    if value.is_empty() || value.starts_with('.') || value.contains(['/', '\\']) {
+        Err(format!("{} is not a user name", value))
+    } else {
+        Ok(value.to_string())
+    }
+}
+
+fn parse_jobs(value: &str) -> Result<usize, String> {
+    match value.parse::<usize>() {
+        Ok(jobs) if jobs > 0 => Ok(jobs),
+        _ => Err(format!("{} is not a number of jobs", value)),
+    }
+}
+
+fn parse_timeout(value: &str) -> Result<Duration, String> {
+    match value.parse::<f64>() {
+        Ok(seconds) if seconds > 0.0 && seconds.is_finite() => Ok(Duration::from_secs_f64(seconds)),
+        _ => Err(format!("{} is not a number of seconds", value)),
+    }
+}
+
+fn parse_threshold(value: &str) -> Result<f64, String> {
+    match value.trim_end_matches('%').parse::<f64>() {
+        Ok(percent) if percent >= 0.0 && percent.is_finite() => Ok(percent),
+        _ => Err(format!("{} is not a percentage", value)),
+    }
+}
+
+fn parse_day(value: &str) -> Result<u32, String> {
+    let day = value
+        .parse::<u32>()
+        .map_err(|_| format!("{} is not a day", value))?;
+    if (1..=25).contains(&day) {
+        Ok(day)
+    } else {
+        Err(format!("unknown day {}, expected 1 to 25", day))
+    }
+}
+
+// the last day of FROM..TO, which is not part of the range itself, so 26
+// is allowed and ends the range with day 25
+fn parse_exclusive_end(value: &str) -> Result<u32, String> {
+    match value.parse::<u32>() {
+        Ok(26) => Ok(25),
+        _ => Ok(parse_day(value)? - 1),
+    }
+}
+
+fn parse_days(value: &str) -> Result<Days, String> {
+    if value == "all" {
+        return Ok(Days::All);
+    }
+
+    // FROM..=TO includes TO, FROM..TO does not, just like in rust
+    let range = match value.split_once("..=") {
+        Some((from, to)) => Some((parse_day(from)?, parse_day(to)?)),
+        None => match value.split_once("..") {
+            Some((from, to)) => Some((parse_day(from)?, parse_exclusive_end(to)?)),
+            None => None,
+        },
+    };
+
+    match range {
+        Some((from, to)) if from > to => Err(format!("empty range {}", value)),
+        Some((from, to)) => Ok(Days::Range(from, to)),
+        None => Ok(Days::Single(parse_day(value)?)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_single_day() {
+        let command = parse_args(&args("run 7")).unwrap();
+        assert_eq!(
+            command,
+            Command::Run {
+                days: Days::Single(7),
+                part: None,
+                record: false,
+                format: Format::Text,
+                timeout: None,
+                input: None,
+                users: Users::Shared,
+                normalize: false,
+                cache: Mode::Off,
+                jobs: None
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_part() {
+        let command = parse_args(&args("run 7 --part 2")).unwrap();
+        assert_eq!(
+            command,
+            Command::Run {
+                days: Days::Single(7),
+                part: Some(2),
+                record: false,
+                format: Format::Text,
+                timeout: None,
+                input: None,
+                users: Users::Shared,
+                normalize: false,
+                cache: Mode::Off,
+                jobs: None
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_range() {
+        let command = parse_args(&args("run 3..9")).unwrap();
+        assert_eq!(
+            command,
+            Command::Run {
+                days: Days::Range(3, 8),
+                part: None,
+                record: false,
+                format: Format::Text,
+                timeout: None,
+                input: None,
+                users: Users::Shared,
+                normalize: false,
+                cache: Mode::Off,
+                jobs: None
+            }
+        );
+        let command = parse_args(&args("3..=9")).unwrap();
+        assert_eq!(
+            command,
+            Command::Run {
+                days: Days::Range(3, 9),
+                part: None,
+                record: false,
+                format: Format::Text,
+                timeout: None,
+                input: None,
+                users: Users::Shared,
+                normalize: false,
+                cache: Mode::Off,
+                jobs: None
+            }
+        );
+        let command = parse_args(&args("run 1..26")).unwrap();
+        assert!(matches!(
+            command,
+            Command::Run {
+                days: Days::Range(1, 25),
+                ..
+            }
+        ));
+        assert_eq!(
+            parse_args(&args("run 1..27")).unwrap_err(),
+            "unknown day 27, expected 1 to 25"
+        );
+    }
+
+    #[test]
+    fn test_parse_record() {
+        let command = parse_args(&args("run 7 --record")).unwrap();
+        assert_eq!(
+            command,
+            Command::Run {
+                days: Days::Single(7),
+                part: None,
+                record: true,
+                format: Format::Text,
+                timeout: None,
+                input: None,
+                users: Users::Shared,
+                normalize: false,
+                cache: Mode::Off,
+                jobs: None
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_format() {
+        let command = parse_args(&args("run all --format json")).unwrap();
+        assert_eq!(
+            command,
+            Command::Run {
+                days: Days::All,
+                part: None,
+                record: false,
+                format: Format::Json,
+                timeout: None,
+                input: None,
+                users: Users::Shared,
+                normalize: false,
+                cache: Mode::Off,
+                jobs: None
+            }
+        );
+        assert_eq!(
+            parse_args(&args("--format xml")).unwrap_err(),
+            "unknown format xml, expected text, json or junit"
+        );
+    }
+
+    #[test]
+    fn test_parse_input() {
+        let command = parse_args(&args("run 7 --input -")).unwrap();
+        assert_eq!(
+            command,
+            Command::Run {
+                days: Days::Single(7),
+                part: None,
+                record: false,
+                format: Format::Text,
+                timeout: None,
+                input: Some(Source::Stdin),
+                users: Users::Shared,
+                normalize: false,
+                cache: Mode::Off,
+                jobs: None
+            }
+        );
+        let command = parse_args(&args("7 --input /tmp/day7.txt")).unwrap();
+        assert!(matches!(
+            command,
+            Command::Run {
+                input: Some(Source::File(_)),
+                ..
+            }
+        ));
+        assert_eq!(
+            parse_args(&args("run 3..9 --input -")).unwrap_err(),
+            "--input needs a single day"
+        );
+        assert_eq!(
+            parse_args(&args("run 7 --input")).unwrap_err(),
+            "--input needs a path or -"
+        );
+    }
+
+    #[test]
+    fn test_parse_users() {
+        let command = parse_args(&args("run 7 --user alice")).unwrap();
+        assert!(
+            matches!(command, Command::Run { users: Users::Named(name), .. } if name == "alice")
+        );
+        let command = parse_args(&args("run --all-users")).unwrap();
+        assert!(matches!(
+            command,
+            Command::Run {
+                days: Days::All,
+                users: Users::All,
+                ..
+            }
+        ));
+        assert_eq!(
+            parse_args(&args("run 7 --user ../bob")).unwrap_err(),
+            "../bob is not a user name"
+        );
+        assert_eq!(
+            parse_args(&args("run 7 --user alice --input -")).unwrap_err(),
+            "--input can not be combined with --user or --all-users"
+        );
+    }
+
+    #[test]
+    fn test_parse_normalize() {
+        let command = parse_args(&args("run 6 --normalize")).unwrap();
+        assert!(matches!(
+            command,
+            Command::Run {
+                normalize: true,
+                ..
+            }
+        ));
+        assert_eq!(
+            parse_args(&args("bench 6 --normalize")).unwrap_err(),
+            "unknown option --normalize"
+        );
+    }
+
+    #[test]
+    fn test_parse_cache() {
+        assert!(matches!(
+            parse_args(&args("run --cache")).unwrap(),
+            Command::Run {
+                cache: Mode::Use,
+                ..
+            }
+        ));
+        assert!(matches!(
+            parse_args(&args("run 7 --force")).unwrap(),
+            Command::Run {
+                cache: Mode::Refresh,
+                ..
+            }
+        ));
+        assert!(matches!(
+            parse_args(&args("run 7 --force --cache")).unwrap(),
+            Command::Run {
+                cache: Mode::Refresh,
+                ..
+            }
+        ));
+        assert_eq!(
+            parse_args(&args("bench 7 --cache")).unwrap_err(),
+            "unknown option --cache"
+        );
+    }
+
+    #[test]
+    fn test_parse_jobs() {
+        assert!(matches!(
+            parse_args(&args("run all --jobs 4")).unwrap(),
+            Command::Run { jobs: Some(4), .. }
+        ));
+        assert!(matches!(
+            parse_args(&args("-j 2")).unwrap(),
+            Command::Run { jobs: Some(2), .. }
+        ));
+        assert_eq!(
+            parse_args(&args("run --jobs 0")).unwrap_err(),
+            "0 is not a number of jobs"
+        );
+        assert_eq!(
+            parse_args(&args("bench --jobs 2")).unwrap_err(),
+            "unknown option --jobs"
+        );
+    }
+
+    #[test]
+    fn test_parse_timeout() {
+        let command = parse_args(&args("bench 5 --timeout 2.5")).unwrap();
+        assert_eq!(
+            command,
+            Command::Bench {
+                days: Days::Single(5),
+                part: None,
+                samples: None,
+                timeout: Some(Duration::from_millis(2500))
+            }
+        );
+        assert_eq!(
+            parse_args(&args("run 5 --timeout 0")).unwrap_err(),
+            "0 is not a number of seconds"
+        );
+    }
+
+    #[test]
+    fn test_parse_bench() {
+        let command = parse_args(&args("bench 3..=9 --samples 20")).unwrap();
+        assert_eq!(
+            command,
+            Command::Bench {
+                days: Days::Range(3, 9),
+                part: None,
+                samples: Some(20),
+                timeout: None
+            }
+        );
+        assert_eq!(
+            parse_args(&args("bench 7 --record")).unwrap_err(),
+            "unknown option --record"
+        );
+    }
+
+    #[test]
+    fn test_parse_watch() {
+        assert_eq!(
+            parse_args(&args("watch 7 --rebuild")).unwrap(),
+            Command::Watch {
+                day: 7,
+                part: None,
+                timeout: None,
+                rebuild: true
+            }
+        );
+        assert_eq!(
+            parse_args(&args("watch all")).unwrap_err(),
+            "watch needs a single day"
+        );
+        assert_eq!(
+            parse_args(&args("watch 7 --record")).unwrap_err(),
+            "unknown option --record"
+        );
+        assert_eq!(
+            parse_args(&args("run 7 --rebuild")).unwrap_err(),
+            "unknown option --rebuild"
+        );
+    }
+
+    #[test]
+    fn test_parse_new() {
+        assert_eq!(
+            parse_args(&args("new 12")).unwrap(),
+            Command::New { day: 12 }
+        );
+        assert_eq!(parse_args(&args("new")).unwrap_err(), "new needs a day");
+        assert_eq!(
+            parse_args(&args("new 12 13")).unwrap_err(),
+            "unexpected argument 13"
+        );
+    }
+
+    #[test]
+    fn test_parse_fetch() {
+        assert_eq!(
+            parse_args(&args("fetch 12")).unwrap(),
+            Command::Fetch { day: 12 }
+        );
+        assert_eq!(parse_args(&args("fetch")).unwrap_err(), "fetch needs a day");
+        assert_eq!(
+            parse_args(&args("fetch 26")).unwrap_err(),
+            "unknown day 26, expected 1 to 25"
+        );
+        assert_eq!(
+            parse_args(&args("fetch 3..5")).unwrap_err(),
+            "3..5 is not a day"
+        );
+    }
+
+    #[test]
+    fn test_parse_submit() {
+        assert_eq!(
+            parse_args(&args("submit 7 2")).unwrap(),
+            Command::Submit { day: 7, part: 2 }
+        );
+        assert_eq!(
+            parse_args(&args("submit 7")).unwrap_err(),
+            "submit needs a day and a part"
+        );
+        assert_eq!(
+            parse_args(&args("submit 7 3")).unwrap_err(),
+            "unknown part 3, expected 1 or 2"
+        );
+    }
+
+    #[test]
+    fn test_parse_readme() {
+        assert_eq!(parse_args(&args("readme")).unwrap(), Command::Readme);
+        assert_eq!(
+            parse_args(&args("readme 7")).unwrap_err(),
+            "unexpected argument 7"
+        );
+    }
+
+    #[test]
+    fn test_parse_compare() {
+        assert_eq!(
+            parse_args(&args("compare")).unwrap(),
+            Command::Compare {
+                baseline: None,
+                threshold: None
+            }
+        );
+        assert_eq!(
+            parse_args(&args("compare --baseline 4c8e1f2 --threshold 25%")).unwrap(),
+            Command::Compare {
+                baseline: Some("4c8e1f2".to_string()),
+                threshold: Some(25.0)
+            }
+        );
+        assert_eq!(
+            parse_args(&args("compare --threshold fast")).unwrap_err(),
+            "fast is not a percentage"
+        );
+        assert_eq!(
+            parse_args(&args("compare 7")).unwrap_err(),
+            "unexpected argument 7"
+        );
+    }
+
+    #[test]
+    fn test_parse_all() {
+        assert_eq!(
+            parse_args(&args("run all")).unwrap(),
+            parse_args(&[]).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse_args(&args("run 26")).unwrap_err(),
+            "unknown day 26, expected 1 to 25"
+        );
+        assert_eq!(
+            parse_args(&args("run 7 --part 3")).unwrap_err(),
+            "unknown part 3, expected 1 or 2"
+        );
+        assert_eq!(
+            parse_args(&args("run 9..3")).unwrap_err(),
+            "empty range 9..3"
+        );
+        assert_eq!(
+            parse_args(&args("run seven")).unwrap_err(),
+            "seven is not a day"
+        );
+    }
+
+    #[test]
+    fn test_select() {
+        let available = [1, 2, 3, 4, 6, 7];
+        assert_eq!(Days::Range(3, 6).select(&available).unwrap(), vec![3, 4, 6]);
+        assert_eq!(
+            Days::Single(5).select(&available).unwrap_err(),
+            "day 5 is not implemented"
+        );
+        assert_eq!(Days::All.select(&available).unwrap(), available.to_vec());
+    }
+}
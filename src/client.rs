@@ -0,0 +1,275 @@
+// the http client for adventofcode.com, it downloads puzzle inputs
+//
+// the session cookie is taken from the AOC_SESSION environment variable or
+// from the file .session next to the input directory, AOC_URL replaces the
+// address of the website, e.g. with a local server for testing
+
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::input;
+
+pub const YEAR: u32 = 2023;
+
+const DEFAULT_URL: &str = "https://adventofcode.com";
+
+// the website asks automated tools to say who they are and where they come from
+pub const USER_AGENT: &str = concat!(
+    "advent_of_code_2023/",
+    env!("CARGO_PKG_VERSION"),
+    " (a personal puzzle runner, set AOC_USER_AGENT to add your contact)"
+);
+
+// the least time between two requests, also between runs of the runner
+pub const MIN_INTERVAL: Duration = Duration::from_secs(5);
+
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+    min_interval: Duration,
+    // holds the time of the last request, in milliseconds since 1970
+    throttle_file: PathBuf,
+}
+
+pub fn session_file() -> PathBuf {
+    input::input_dir().with_file_name(".session")
+}
+
+fn throttle_file() -> PathBuf {
+    input::input_dir().with_file_name(".last_request")
+}
+
+// the cookie value, with or without the session= in front
+pub fn session() -> Result<String, String> {
+    let session = match std::env::var("AOC_SESSION") {
+        Ok(session) => session,
+        Err(_) => std::fs::read_to_string(session_file()).map_err(|_| {
+            format!(
+                "no session cookie, set AOC_SESSION or write it to {}",
+                session_file().display()
+            )
+        })?,
+    };
+    let session = session.trim();
+    let session = session.strip_prefix("session=").unwrap_or(session);
+    if session.is_empty() {
+        return Err("the session cookie is empty".to_string());
+    }
+    Ok(session.to_string())
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+impl Client {
+    // the client the runner uses, configured from the environment
+    pub fn from_env() -> Result<Client, String> {
+        let base_url = std::env::var("AOC_URL").unwrap_or_else(|_| DEFAULT_URL.to_string());
+        Ok(Client::new(
+            &base_url,
+            &session()?,
+            MIN_INTERVAL,
+            &throttle_file(),
+        ))
+    }
+
+    pub fn new(
+        base_url: &str,
+        session: &str,
+        min_interval: Duration,
+        throttle_file: &Path,
+    ) -> Client {
+        let user_agent = std::env::var("AOC_USER_AGENT").unwrap_or_else(|_| USER_AGENT.to_string());
+        Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            agent: ureq::AgentBuilder::new()
+                .user_agent(&user_agent)
+                .timeout(Duration::from_secs(30))
+                .build(),
+            min_interval,
+            throttle_file: throttle_file.to_path_buf(),
+        }
+    }
+
+    pub fn url(&self, path: &str) -> String {
+        format!("{}/{}/day/{}", self.base_url, YEAR, path)
+    }
+
+    // waits until the last request is at least min_interval ago
+    fn throttle(&self) {
+        let last = std::fs::read_to_string(&self.throttle_file)
+            .ok()
+            .and_then(|content| content.trim().parse::<u64>().ok())
+            .unwrap_or(0);
+        // the stored time is cut to whole milliseconds, the request may have
+        // been up to one later
+        let next = last + self.min_interval.as_millis() as u64 + 1;
+        let now = now_millis();
+        if next > now {
+            thread::sleep(Duration::from_millis(next - now));
+        }
+        let _ = std::fs::write(&self.throttle_file, now_millis().to_string());
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    // the body of a successful response, the status code and body of any other
+    fn finish(response: Result<ureq::Response, ureq::Error>) -> Result<String, (u16, String)> {
+        match response {
+            Ok(response) => response.into_string().map_err(|e| (0, e.to_string())),
+            Err(ureq::Error::Status(code, response)) => {
+                Err((code, response.into_string().unwrap_or_default()))
+            }
+            Err(ureq::Error::Transport(e)) => Err((0, e.to_string())),
+        }
+    }
+
+    pub fn get(&self, path: &str) -> Result<String, (u16, String)> {
+        self.throttle();
+        Client::finish(
+            self.agent
+                .get(&self.url(path))
+                .set("Cookie", &self.cookie())
+                .call(),
+        )
+    }
+
+    pub fn post_form(&self, path: &str, form: &[(&str, &str)]) -> Result<String, (u16, String)> {
+        self.throttle();
+        Client::finish(
+            self.agent
+                .post(&self.url(path))
+                .set("Cookie", &self.cookie())
+                .send_form(form),
+        )
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Fetched {
+    Downloaded(usize),
+    // the file was there already, inputs never change
+    Cached,
+}
+
+// downloads the input of a day to `path` unless it is there already, an
+// empty file is what new leaves and is downloaded; the client is only made
+// for a download, so an input that is there needs no session
+pub fn fetch(
+    client: impl FnOnce() -> Result<Client, String>,
+    day: u32,
+    path: &Path,
+) -> Result<Fetched, String> {
+    if std::fs::metadata(path).is_ok_and(|m| m.len() > 0) {
+        return Ok(Fetched::Cached);
+    }
+    let content = client()?
+        .get(&format!("{}/input", day))
+        .map_err(|(code, body)| match code {
+            0 => format!("Could not download the input of day {}: {}", day, body),
+            400 | 500 => {
+                "the website did not accept the session cookie, it may have expired".to_string()
+            }
+            404 => format!("the input of day {} is not available yet", day),
+            _ => format!(
+                "Could not download the input of day {}: status {}",
+                day, code
+            ),
+        })?;
+    // written under another name first, so an interrupted download leaves no broken input
+    let partial = path.with_extension("txt.part");
+    std::fs::write(&partial, &content)
+        .and_then(|_| std::fs::rename(&partial, path))
+        .map_err(|e| format!("Could not write {}: {}", path.display(), e))?;
+    Ok(Fetched::Downloaded(content.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{serve, temp_path};
+
+    fn client(url: &str) -> Client {
+        Client::new(
+            url,
+            "53616c7465645f5f",
+            Duration::ZERO,
+            &temp_path("last_request"),
+        )
+    }
+
+    #[test]
+    fn test_fetch() {
+        let (url, requests) = serve(vec![(200, "32T3K 765\nT55J5 684\n")]);
+        let path = temp_path("day7.txt");
+        assert_eq!(
+            fetch(|| Ok(client(&url)), 7, &path),
+            Ok(Fetched::Downloaded(20))
+        );
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "32T3K 765\nT55J5 684\n"
+        );
+
+        let request = requests.recv().unwrap();
+        assert!(
+            request.starts_with("GET /2023/day/7/input HTTP/1.1\r\n"),
+            "{}",
+            request
+        );
+        assert!(
+            request.contains("Cookie: session=53616c7465645f5f\r\n"),
+            "{}",
+            request
+        );
+        assert!(request.contains("advent_of_code_2023/"), "{}", request);
+
+        assert_eq!(fetch(|| Ok(client(&url)), 7, &path), Ok(Fetched::Cached));
+        let no_session = || Err("no session".to_string());
+        assert_eq!(fetch(no_session, 7, &path), Ok(Fetched::Cached));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_fetch_errors() {
+        let (url, _requests) = serve(vec![
+            (404, "Not Found"),
+            (400, "Puzzle inputs differ by user."),
+        ]);
+        let path = temp_path("day25.txt");
+        assert_eq!(
+            fetch(|| Ok(client(&url)), 25, &path).unwrap_err(),
+            "the input of day 25 is not available yet"
+        );
+        assert_eq!(
+            fetch(|| Ok(client(&url)), 25, &path).unwrap_err(),
+            "the website did not accept the session cookie, it may have expired"
+        );
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_throttle() {
+        let throttle_file = temp_path("throttle");
+        let client = Client::new(
+            "http://127.0.0.1:9",
+            "s",
+            Duration::from_millis(200),
+            &throttle_file,
+        );
+        let start = std::time::Instant::now();
+        client.throttle();
+        client.throttle();
+        assert!(start.elapsed() >= Duration::from_millis(200));
+        std::fs::remove_file(&throttle_file).unwrap();
+    }
+}
@@ -0,0 +1,35 @@
+// how a day is run, set from the command line and passed down to every part
+
+use std::time::Duration;
+
+use crate::cache;
+use crate::input::Source;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    // results without a known answer are written to the answers file
+    pub record: bool,
+    // how long parse and each part may take, None waits for them forever
+    pub timeout: Option<Duration>,
+    // when set, every input file name is read from here instead
+    pub source: Option<Source>,
+    // removes what src/lint.rs warns about before solving
+    pub normalize: bool,
+    pub cache: cache::Mode,
+    // with more than one job the two parts of a day run at the same time
+    pub jobs: usize,
+}
+
+// what the solution functions the tests call use, AOC_RECORD turns recording on
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            record: std::env::var_os("AOC_RECORD").is_some(),
+            timeout: None,
+            source: None,
+            normalize: false,
+            cache: cache::Mode::Off,
+            jobs: 1,
+        }
+    }
+}
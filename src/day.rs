@@ -0,0 +1,276 @@
+// the Day trait every module implements and the registry the runner uses to find them
+
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use crate::answer::Answer;
+use crate::bench::{format_duration, measure, BenchConfig, Stats};
+use crate::config::Config;
+use crate::outcome::Outcome;
+use crate::watchdog::{self, Failure};
+use crate::{cache_solution, cached_solution, read_input, run_both, run_part};
+
+pub trait Day {
+    const DAY: u32;
+    // change it when the answers may change, so cached ones are not used, see src/cache.rs
+    const VERSION: &'static str;
+    type Input: Send + Sync + 'static;
+    type Output1: Into<Answer> + Send + 'static;
+    type Output2: Into<Answer> + Send + 'static;
+
+    // the base name of the input files, e.g. day7 for input/day7.txt
+    fn name() -> String {
+        format!("day{}", Self::DAY)
+    }
+
+    // gets the whole input file, the result may not borrow from it, a day that
+    // works on the text keeps it as one String; parse is timed on its own and
+    // runs once for both parts, which share its result
+    fn parse(input: &str) -> Self::Input;
+    fn part1(input: &Self::Input) -> Self::Output1;
+    fn part2(input: &Self::Input) -> Self::Output2;
+}
+
+// object safe view on a Day, so days with different types can be stored together
+// and run on several threads
+pub trait Solver: Sync {
+    fn day(&self) -> u32;
+    fn name(&self) -> String;
+    fn run(&self, part: Option<u8>, config: &Config) -> Vec<Outcome> {
+        self.run_on(&self.name(), part, config)
+    }
+    // runs on another input file than the one named after the day, e.g. alice/day7
+    fn run_on(&self, input_file: &str, part: Option<u8>, config: &Config) -> Vec<Outcome>;
+    // measures the selected parts on the real input, None if it can not be read,
+    // parts that do not finish a first run within the watchdog deadline are not
+    // measured and neither is anything after them
+    fn bench(
+        &self,
+        part: Option<u8>,
+        config: &BenchConfig,
+    ) -> Option<Vec<(u8, Result<Stats, String>)>>;
+}
+
+// fn() -> D keeps the entry Sync whatever D is
+struct Entry<D: Day>(PhantomData<fn() -> D>);
+
+impl<D: Day + 'static> Solver for Entry<D> {
+    fn day(&self) -> u32 {
+        D::DAY
+    }
+
+    fn name(&self) -> String {
+        D::name()
+    }
+
+    fn run_on(&self, input_file: &str, part: Option<u8>, config: &Config) -> Vec<Outcome> {
+        let parts: &[&str] = match part {
+            Some(1) => &["1"],
+            Some(2) => &["2"],
+            _ => &["1", "2"],
+        };
+        if let Some(outcomes) = cached_solution(input_file, &D::name(), D::VERSION, parts, config) {
+            return outcomes;
+        }
+        let parse = |content: String| Arc::new(D::parse(&content));
+        let part1 = |input: Arc<D::Input>| D::part1(&input);
+        let part2 = |input: Arc<D::Input>| D::part2(&input);
+        let outcomes = match part {
+            Some(1) => vec![run_part(input_file, "1", config, parse, part1)],
+            Some(2) => vec![run_part(input_file, "2", config, parse, part2)],
+            _ => run_both(input_file, config, parse, part1, part2),
+        };
+        if let Err(message) = cache_solution(input_file, &D::name(), D::VERSION, &outcomes, config)
+        {
+            eprintln!("warning: {}", message);
+        }
+        outcomes
+    }
+
+    fn bench(
+        &self,
+        part: Option<u8>,
+        config: &BenchConfig,
+    ) -> Option<Vec<(u8, Result<Stats, String>)>> {
+        let (content, _) = read_input(&D::name(), &Config::default()).ok()?;
+        let input = Arc::new(D::parse(&content));
+        let mut results = Vec::new();
+        if part != Some(2) {
+            results.push((1, bench_part(config, &input, D::part1)));
+        }
+        if part != Some(1) {
+            results.push((2, bench_part(config, &input, D::part2)));
+        }
+        Some(results)
+    }
+}
+
+fn bench_part<I, O>(
+    config: &BenchConfig,
+    input: &Arc<I>,
+    solve: fn(&I) -> O,
+) -> Result<Stats, String>
+where
+    I: Send + Sync + 'static,
+    O: Send + 'static,
+{
+    // the core a timed out part keeps busy would skew the measurement
+    if watchdog::runaway() > 0 {
+        return Err("not measured, a part that timed out is still running".to_string());
+    }
+    let trial_input = Arc::clone(input);
+    match watchdog::run(config.timeout, move || solve(&trial_input)) {
+        Ok(_) => Ok(measure(config, || (), |_| solve(input))),
+        Err(Failure::Panicked(message)) => Err(format!("panicked: {}", message)),
+        Err(Failure::Timeout(timeout)) => {
+            Err(format!("no result within {}", format_duration(timeout)))
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Registry {
+    solvers: Vec<Box<dyn Solver>>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // the day itself is only passed to name the type
+    pub fn register<D: Day + 'static>(&mut self, _day: D) {
+        if self.get(D::DAY).is_some() {
+            panic!("day {} is registered twice", D::DAY);
+        }
+        self.solvers.push(Box::new(Entry::<D>(PhantomData)));
+        self.solvers.sort_by_key(|solver| solver.day());
+    }
+
+    pub fn get(&self, day: u32) -> Option<&dyn Solver> {
+        self.solvers
+            .iter()
+            .find(|solver| solver.day() == day)
+            .map(|solver| solver.as_ref())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Solver> {
+        self.solvers.iter().map(|solver| solver.as_ref())
+    }
+
+    // the numbers of all registered days in ascending order
+    pub fn days(&self) -> Vec<u32> {
+        self.iter().map(|solver| solver.day()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::input::Source;
+    use crate::outcome::Status;
+    use crate::testing::temp_path;
+
+    struct Dummy<const N: u32>;
+
+    impl<const N: u32> Day for Dummy<N> {
+        const DAY: u32 = N;
+        const VERSION: &'static str = "1";
+        type Input = String;
+        type Output1 = usize;
+        type Output2 = usize;
+
+        fn parse(input: &str) -> String {
+            input.to_string()
+        }
+
+        fn part1(input: &String) -> usize {
+            input.lines().count()
+        }
+
+        fn part2(input: &String) -> usize {
+            input.len()
+        }
+    }
+
+    #[test]
+    fn test_registry_is_sorted() {
+        let mut registry = Registry::new();
+        registry.register(Dummy::<9>);
+        registry.register(Dummy::<2>);
+        registry.register(Dummy::<15>);
+        assert_eq!(registry.days(), vec![2, 9, 15]);
+        assert_eq!(registry.get(9).unwrap().name(), "day9");
+        assert!(registry.get(3).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "day 2 is registered twice")]
+    fn test_register_twice() {
+        let mut registry = Registry::new();
+        registry.register(Dummy::<2>);
+        registry.register(Dummy::<2>);
+    }
+
+    #[test]
+    fn test_run_on_another_source() {
+        let path = temp_path("source.txt");
+        std::fs::write(&path, "a\nbb\n").unwrap();
+        let config = Config {
+            source: Some(Source::File(path.clone())),
+            record: true,
+            ..Config::default()
+        };
+        let outcomes = Entry::<Dummy<1>>(PhantomData).run_on("day1", None, &config);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(outcomes.len(), 2);
+        for outcome in &outcomes {
+            assert_eq!(outcome.input, path.display().to_string());
+            // there is no answer for another source and none is recorded
+            assert_eq!(outcome.status, Status::Unknown);
+        }
+        assert_eq!(outcomes[0].value, Some(Answer::from(2)));
+        assert_eq!(outcomes[1].value, Some(Answer::from(5)));
+    }
+
+    static PARSED: AtomicUsize = AtomicUsize::new(0);
+
+    struct CountParses;
+
+    impl Day for CountParses {
+        const DAY: u32 = 1;
+        const VERSION: &'static str = "1";
+        type Input = Vec<String>;
+        type Output1 = usize;
+        type Output2 = usize;
+
+        fn parse(input: &str) -> Vec<String> {
+            PARSED.fetch_add(1, Ordering::SeqCst);
+            input.lines().map(|line| line.to_string()).collect()
+        }
+
+        fn part1(lines: &Vec<String>) -> usize {
+            lines.len()
+        }
+
+        fn part2(lines: &Vec<String>) -> usize {
+            lines.iter().map(|line| line.len()).sum()
+        }
+    }
+
+    #[test]
+    fn test_both_parts_share_one_parse() {
+        let config = Config {
+            record: false,
+            ..Config::default()
+        };
+        let outcomes = Entry::<CountParses>(PhantomData).run_on("day1_test", None, &config);
+        assert_eq!(PARSED.load(Ordering::SeqCst), 1);
+        assert_eq!(outcomes[0].value, Some(Answer::from(4)));
+        // the one parse is reported for both parts
+        assert_eq!(outcomes[0].timings.parse, outcomes[1].timings.parse);
+    }
+}
@@ -2,15 +2,16 @@
 
 use std::collections::HashMap;
 
+use advent_of_code_2023::day::Day;
 use advent_of_code_2023::ReverseString;
 
-pub fn solve1(input: Vec<String>) -> i32 {
+pub fn solve1(input: &str) -> i32 {
     let mut sum = 0;
-    for line in input {
+    for line in input.lines() {
         // find the first digit in the string
         let first_digit = line
             .chars()
-            .find(|c| c.is_digit(10))
+            .find(|c| c.is_ascii_digit())
             .unwrap()
             .to_digit(10)
             .unwrap() as i32;
@@ -18,7 +19,7 @@ pub fn solve1(input: Vec<String>) -> i32 {
         let second_digit = line
             .chars()
             .rev()
-            .find(|c| c.is_digit(10))
+            .find(|c| c.is_ascii_digit())
             .unwrap()
             .to_digit(10)
             .unwrap() as i32;
@@ -27,7 +28,7 @@ pub fn solve1(input: Vec<String>) -> i32 {
     sum
 }
 
-pub fn solve2(input: Vec<String>) -> i32 {
+pub fn solve2(input: &str) -> i32 {
     let mut sum = 0;
     let names_to_digit: HashMap<&str, i32> = [
         // ("zero", 0),
@@ -42,20 +43,20 @@ pub fn solve2(input: Vec<String>) -> i32 {
         ("nine", 9),
     ].iter().cloned().collect();
     let regex_pattern_forward = "(".to_owned() + &names_to_digit.keys()
-        .map(|k| format!("{}", k))
+        .map(|k| k.to_string())
         .collect::<Vec<_>>()
         .join("|") + "|\\d)";
     let regex_forwards = regex::Regex::new(&regex_pattern_forward).unwrap();
 
     let regex_pattern_backwards = "(".to_owned() + &names_to_digit.keys()
-        .map(|k| format!("{}", k.reverse()))
+        .map(|k| k.reverse().to_string())
         .collect::<Vec<_>>()
         .join("|") + "|\\d)";
     let regex_backwards = regex::Regex::new(&regex_pattern_backwards).unwrap();
 
-    for line in input {
+    for line in input.lines() {
         let rev_line = line.reverse();
-        let first_result = regex_forwards.find_iter(&line).next().unwrap();
+        let first_result = regex_forwards.find_iter(line).next().unwrap();
         let last_result = regex_backwards.find_iter(&rev_line).next().unwrap();
 
         let first_matched = first_result.as_str();
@@ -71,41 +72,65 @@ pub fn solve2(input: Vec<String>) -> i32 {
 }
 
 fn convert(names_to_digit: &HashMap<&str, i32>, matched: &str) -> i32 {
-    if matched.chars().all(|c| c.is_digit(10)) {
+    if matched.chars().all(|c| c.is_ascii_digit()) {
         matched.parse::<i32>().unwrap()
     } else {
         *names_to_digit.get(matched).unwrap()
     }
 }
 
+pub struct Day1;
+
+impl Day for Day1 {
+    const DAY: u32 = 1;
+    const VERSION: &'static str = "1";
+    // the solvers go through the lines of the text themselves
+    type Input = String;
+    type Output1 = i32;
+    type Output2 = i32;
+
+    fn parse(input: &str) -> String {
+        input.to_string()
+    }
+
+    fn part1(input: &String) -> i32 {
+        solve1(input)
+    }
+
+    fn part2(input: &String) -> i32 {
+        solve2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use advent_of_code_2023::solution_lines;
+    use advent_of_code_2023::day::Day;
+    use advent_of_code_2023::solution_parse_str;
 
-    use crate::day1::{solve1, solve2};
+    use crate::day1::Day1;
 
     // test the functions
     #[test]
     fn test_solve1_test() {
-        let test = solution_lines("day1_test", solve1, 142);
-        assert!(test);
+        let test = solution_parse_str("day1_test", "1", Day1::parse, Day1::part1);
+        assert!(test.is_correct(), "{}", test);
     }
 
     #[test]
     fn test_solve1() {
-        let test = solution_lines("day1", solve1, 55130);
-        assert!(test);
+        let test = solution_parse_str("day1", "1", Day1::parse, Day1::part1);
+        assert!(test.is_correct(), "{}", test);
     }
 
     #[test]
     fn test_solve2_test() {
-        let test = solution_lines("day1_test2", solve2, 281);
-        assert!(test);
+        let test = solution_parse_str("day1_test2", "2", Day1::parse, Day1::part2);
+        assert!(test.is_correct(), "{}", test);
     }
 
     #[test]
     fn test_solve2() {
-        let test = solution_lines("day1", solve2, 54985);
-        assert!(test);
+        let test = solution_parse_str("day1", "2", Day1::parse, Day1::part2);
+        assert!(test.is_correct(), "{}", test);
     }
 }
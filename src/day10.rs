@@ -1,5 +1,8 @@
-#[derive(Debug, PartialEq)]
-struct Field(Vec<Vec<(char, bool)>>);
+use advent_of_code_2023::day::Day;
+
+// the pipes and whether they are part of the loop, which follow_pipe marks
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field(Vec<Vec<(char, bool)>>);
 
 #[derive(Debug, PartialEq)]
 struct Pos(usize, usize);
@@ -13,10 +16,10 @@ enum Direction {
 }
 
 impl Field {
-    fn parse_input(input: &Vec<String>) -> Field {
+    fn parse_input(input: &str) -> Field {
         Field(
             input
-                .iter()
+                .lines()
                 .map(|l| l.chars().map(|c| (c, false)).collect())
                 .collect(),
         )
@@ -26,7 +29,7 @@ impl Field {
         for (y, row) in self.0.iter().enumerate() {
             for (x, c) in row.iter().enumerate() {
                 let p = Pos(x, y);
-                if (*c).0 == 'S' {
+                if c.0 == 'S' {
                     return p;
                 }
             }
@@ -118,7 +121,7 @@ impl Field {
         let x = pos.0;
         let mut y = pos.1;
         while y > 0 {
-            y = y - 1;
+            y -= 1;
 
             let char = self.at_in_loop(&Pos(x, y));
             if "7J-".contains(char) {
@@ -155,57 +158,74 @@ impl Field {
     }
 }
 
-pub fn solve1(lines: Vec<String>) -> usize {
-    let mut field = Field::parse_input(&lines);
+pub fn solve1(field: &Field) -> usize {
+    let mut field = field.clone();
     field.follow_pipe()
 }
 
-pub fn solve2(lines: Vec<String>) -> usize {
-    let mut field = Field::parse_input(&lines);
+pub fn solve2(field: &Field) -> usize {
+    let mut field = field.clone();
     field.follow_pipe();
     field.count_all_inside()
 }
 
+pub struct Day10;
+
+impl Day for Day10 {
+    const DAY: u32 = 10;
+    const VERSION: &'static str = "1";
+    type Input = Field;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Field {
+        Field::parse_input(input)
+    }
+
+    fn part1(field: &Field) -> usize {
+        solve1(field)
+    }
+
+    fn part2(field: &Field) -> usize {
+        solve2(field)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use advent_of_code_2023::solution_lines;
+    use advent_of_code_2023::day::Day;
+    use advent_of_code_2023::solution_parse_str;
 
-    use super::{solve1, solve2, Direction, Field, Pos};
+    use super::{Day10, Direction, Field, Pos};
 
     #[test]
     fn test_solve1_test() {
-        let result = solution_lines("day10_test", solve1, 8);
-        assert!(result)
+        let result = solution_parse_str("day10_test", "1", Day10::parse, Day10::part1);
+        assert!(result.is_correct(), "{}", result);
     }
 
     #[test]
     fn test_solve1() {
-        let result = solution_lines("day10", solve1, 6909);
-        assert!(result)
+        let result = solution_parse_str("day10", "1", Day10::parse, Day10::part1);
+        assert!(result.is_correct(), "{}", result);
     }
 
     #[test]
     fn test_solve2_test() {
-        let result = solution_lines("day10_test", solve2, 1);
-        assert!(result)
+        let result = solution_parse_str("day10_test", "2", Day10::parse, Day10::part2);
+        assert!(result.is_correct(), "{}", result);
     }
 
     #[test]
     fn test_solve2() {
-        let result = solution_lines("day10", solve2, 461);
-        assert!(result)
+        let result = solution_parse_str("day10", "2", Day10::parse, Day10::part2);
+        assert!(result.is_correct(), "{}", result);
     }
 
     #[test]
     fn test_parse_input() {
-        let input = vec![
-            String::from("7-F7-"),
-            String::from(".FJ|7"),
-            String::from("SJLL7"),
-            String::from("|F--J"),
-            String::from("LJ.LJ"),
-        ];
-        let field = Field::parse_input(&input);
+        let input = "7-F7-\n.FJ|7\nSJLL7\n|F--J\nLJ.LJ\n";
+        let field = Field::parse_input(input);
         assert_eq!(field.0.len(), 5);
         assert_eq!(field.0[0].len(), 5);
         assert_eq!(field.0[0][0].0, '7');
@@ -216,14 +236,8 @@ mod tests {
 
     #[test]
     fn test_is_inside() {
-        let input = vec![
-            String::from("7-F7-"),
-            String::from(".FJ|7"),
-            String::from("SJLL7"),
-            String::from("|F--J"),
-            String::from("LJ.LJ"),
-        ];
-        let mut field = Field::parse_input(&input);
+        let input = "7-F7-\n.FJ|7\nSJLL7\n|F--J\nLJ.LJ\n";
+        let mut field = Field::parse_input(input);
         field.follow_pipe();
         let inside = field.is_inside(&Pos(1, 4));
         assert!(!inside)
@@ -231,28 +245,16 @@ mod tests {
 
     #[test]
     fn test_find_start_field() {
-        let input = vec![
-            String::from("7-F7-"),
-            String::from(".FJ|7"),
-            String::from("SJLL7"),
-            String::from("|F--J"),
-            String::from("LJ.LJ"),
-        ];
-        let field = Field::parse_input(&input);
+        let input = "7-F7-\n.FJ|7\nSJLL7\n|F--J\nLJ.LJ\n";
+        let field = Field::parse_input(input);
         let pos = field.find_start_field();
         assert_eq!(pos, Pos(0, 2))
     }
 
     #[test]
     fn test_find_start_direction() {
-        let input = vec![
-            String::from("7-F7-"),
-            String::from(".FJ|7"),
-            String::from("SJLL7"),
-            String::from("|F--J"),
-            String::from("LJ.LJ"),
-        ];
-        let field = Field::parse_input(&input);
+        let input = "7-F7-\n.FJ|7\nSJLL7\n|F--J\nLJ.LJ\n";
+        let field = Field::parse_input(input);
         let pos = field.find_start_field();
         let dir = field.find_start_direction(&pos);
         assert_eq!(dir, Direction::Down)
@@ -1,7 +1,9 @@
+use advent_of_code_2023::day::Day;
+
 #[derive(Debug, Clone, PartialEq)]
 struct Galaxy(usize, usize);
 
-fn find_galaxies(input: &Vec<String>) -> Vec<Galaxy> {
+fn find_galaxies(input: &[&str]) -> Vec<Galaxy> {
     let mut galaxies: Vec<Galaxy> = Vec::new();
     for (y, line) in input.iter().enumerate() {
         for (x, c) in line.chars().enumerate() {
@@ -13,28 +15,25 @@ fn find_galaxies(input: &Vec<String>) -> Vec<Galaxy> {
     galaxies
 }
 
-fn find_empty_rows(input: &Vec<String>) -> Vec<usize> {
-    fn all_dot(s: &String) -> bool {
+fn find_empty_rows(input: &[&str]) -> Vec<usize> {
+    fn all_dot(s: &str) -> bool {
         s.chars().all(|c| c == '.')
     }
 
     input
-        .into_iter()
+        .iter()
         .enumerate()
         .filter(|(_, line)| all_dot(line))
         .map(|(i, _)| i)
         .collect()
 }
 
-fn find_empty_columns(input: &Vec<String>) -> Vec<usize> {
-    fn all_dot(input: &Vec<String>, x: usize) -> bool {
+fn find_empty_columns(input: &[&str]) -> Vec<usize> {
+    fn all_dot(input: &[&str], x: usize) -> bool {
         input.iter().all(|line| line.chars().nth(x).unwrap() == '.')
     }
 
-    (0..input[0].len())
-        .into_iter()
-        .filter(|x| all_dot(input, *x))
-        .collect()
+    (0..input[0].len()).filter(|x| all_dot(input, *x)).collect()
 }
 
 fn enlarge_galaxy_row(galaxies: Vec<Galaxy>, row: usize, growth: usize) -> Vec<Galaxy> {
@@ -65,13 +64,13 @@ fn enlarge_galaxy_column(galaxies: Vec<Galaxy>, col: usize, growth: usize) -> Ve
 
 fn enlarge_all_galaxies(
     galaxies: Vec<Galaxy>,
-    rows: &Vec<usize>,
-    cols: &Vec<usize>,
+    rows: &[usize],
+    cols: &[usize],
     growth: usize,
 ) -> Vec<Galaxy> {
-    let mut rows = rows.clone();
+    let mut rows = rows.to_vec();
     rows.sort_by(|a, b| b.cmp(a));
-    let mut cols = cols.clone();
+    let mut cols = cols.to_vec();
     cols.sort_by(|a, b| b.cmp(a));
 
     let mut galaxies = galaxies;
@@ -86,12 +85,12 @@ fn enlarge_all_galaxies(
 }
 
 fn calculate_distance(a: &Galaxy, b: &Galaxy) -> usize {
-    let dx = if a.0 > b.0 { a.0 - b.0 } else { b.0 - a.0 };
-    let dy = if a.1 > b.1 { a.1 - b.1 } else { b.1 - a.1 };
-    return dx + dy;
+    let dx = a.0.abs_diff(b.0);
+    let dy = a.1.abs_diff(b.1);
+    dx + dy
 }
 
-fn find_all_distances(galaxies: &Vec<Galaxy>) -> Vec<usize> {
+fn find_all_distances(galaxies: &[Galaxy]) -> Vec<usize> {
     let mut distances: Vec<usize> = Vec::new();
     for (i, g1) in galaxies.iter().enumerate() {
         for g2 in galaxies.iter().skip(i + 1) {
@@ -101,78 +100,113 @@ fn find_all_distances(galaxies: &Vec<Galaxy>) -> Vec<usize> {
     distances
 }
 
-fn solve_with_growth(input: Vec<String>, growth: usize) -> usize {
-    let galaxies = find_galaxies(&input);
-    let empty_rows = find_empty_rows(&input);
-    let empy_cols = find_empty_columns(&input);
-    let galaxies = enlarge_all_galaxies(galaxies, &empty_rows, &empy_cols, growth);
+// the galaxies and the rows and columns without any
+pub struct Image {
+    galaxies: Vec<Galaxy>,
+    empty_rows: Vec<usize>,
+    empty_cols: Vec<usize>,
+}
+
+fn parse_image(input: &str) -> Image {
+    let lines: Vec<&str> = input.lines().collect();
+    Image {
+        galaxies: find_galaxies(&lines),
+        empty_rows: find_empty_rows(&lines),
+        empty_cols: find_empty_columns(&lines),
+    }
+}
+
+fn solve_with_growth(image: &Image, growth: usize) -> usize {
+    let galaxies = enlarge_all_galaxies(
+        image.galaxies.clone(),
+        &image.empty_rows,
+        &image.empty_cols,
+        growth,
+    );
 
     let distances = find_all_distances(&galaxies);
 
-    distances.iter().map(|d| *d).sum()
+    distances.iter().copied().sum()
 }
 
-pub fn solve1(input: Vec<String>) -> usize {
-    solve_with_growth(input, 1)
+pub fn solve1(image: &Image) -> usize {
+    solve_with_growth(image, 1)
 }
 
-fn solve2_growth_10(input: Vec<String>) -> usize {
-    solve_with_growth(input, 9)
-}
+pub struct Day11;
+
+impl Day for Day11 {
+    const DAY: u32 = 11;
+    const VERSION: &'static str = "1";
+    type Input = Image;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Image {
+        parse_image(input)
+    }
+
+    fn part1(image: &Image) -> usize {
+        solve1(image)
+    }
 
-fn solve2_growth_100(input: Vec<String>) -> usize {
-    solve_with_growth(input, 99)
+    fn part2(image: &Image) -> usize {
+        solve2(image)
+    }
 }
 
-pub fn solve2(input: Vec<String>) -> usize {
-    solve_with_growth(input, 1_000_000 - 1)
+pub fn solve2(image: &Image) -> usize {
+    solve_with_growth(image, 1_000_000 - 1)
 }
 
 #[cfg(test)]
 mod tests {
-    use advent_of_code_2023::{s, solution_lines};
+    use advent_of_code_2023::day::Day;
+    use advent_of_code_2023::{input, solution_parse_str};
 
+    use crate::day11::enlarge_galaxy_row;
     use crate::day11::find_empty_rows;
     use crate::day11::find_galaxies;
     use crate::day11::Galaxy;
-    use crate::day11::{calculate_distance, solve1};
-    use crate::day11::{enlarge_galaxy_column, solve2_growth_10};
-    use crate::day11::{enlarge_galaxy_row, solve2_growth_100};
-    use crate::day11::{find_empty_columns, solve2};
+    use crate::day11::{calculate_distance, Day11};
+    use crate::day11::{enlarge_galaxy_column, solve_with_growth};
+    use crate::day11::{find_empty_columns, parse_image};
 
     #[test]
     fn test_solve1_test() {
-        let result = solution_lines("day11_test", solve1, 374);
-        assert!(result)
+        let result = solution_parse_str("day11_test", "1", Day11::parse, Day11::part1);
+        assert!(result.is_correct(), "{}", result);
     }
 
     #[test]
     fn test_solve1() {
-        let result = solution_lines("day11", solve1, 10228230);
-        assert!(result)
+        let result = solution_parse_str("day11", "1", Day11::parse, Day11::part1);
+        assert!(result.is_correct(), "{}", result);
     }
 
     #[test]
-    fn test_solve2_10_test() {
-        let result = solution_lines("day11_test", solve2_growth_10, 1030);
-        assert!(result)
+    fn test_solve_with_growth_test() {
+        let input = std::fs::read_to_string(input::path("day11_test")).unwrap();
+        let image = parse_image(&input);
+        assert_eq!(solve_with_growth(&image, 9), 1030);
+        assert_eq!(solve_with_growth(&image, 99), 8410);
     }
 
     #[test]
-    fn test_solve2_100_test() {
-        let result = solution_lines("day11_test", solve2_growth_100, 8410);
-        assert!(result)
+    fn test_solve2_test() {
+        let result = solution_parse_str("day11_test", "2", Day11::parse, Day11::part2);
+        assert!(result.is_correct(), "{}", result);
     }
 
     #[test]
     fn test_solve2() {
-        let result = solution_lines("day11", solve2, 447073334102);
-        assert!(result)
+        let result = solution_parse_str("day11", "2", Day11::parse, Day11::part2);
+        assert!(result.is_correct(), "{}", result);
     }
 
     #[test]
     fn test_find_galaxies() {
-        let input = vec![s!("...#......"), s!(".........."), s!("#...#.....")];
+        let input = vec!["...#......", "..........", "#...#....."];
         let result = find_galaxies(&input);
         assert_eq!(result.len(), 3);
         assert_eq!(result[0], Galaxy(3, 0));
@@ -182,24 +216,14 @@ mod tests {
 
     #[test]
     fn test_file_empty_rows() {
-        let input = vec![
-            s!("...#......"),
-            s!(".........."),
-            s!("#...#....."),
-            s!(".........."),
-        ];
+        let input = vec!["...#......", "..........", "#...#.....", ".........."];
         let result = find_empty_rows(&input);
         assert_eq!(result, vec![1, 3]);
     }
 
     #[test]
     fn test_file_empty_columns() {
-        let input = vec![
-            s!("...#......"),
-            s!(".........."),
-            s!("#...#....."),
-            s!(".........."),
-        ];
+        let input = vec!["...#......", "..........", "#...#.....", ".........."];
         let result = find_empty_columns(&input);
         assert_eq!(result, vec![1, 2, 5, 6, 7, 8, 9]);
     }
@@ -1,5 +1,7 @@
 use std::str::FromStr;
 
+use advent_of_code_2023::day::Day;
+
 fn hash_str(s: &str) -> i64 {
     let mut hash = 0;
 
@@ -10,10 +12,9 @@ fn hash_str(s: &str) -> i64 {
     hash
 }
 
-pub fn solve1(lines: Vec<String>) -> i64 {
-    let line = &lines[0];
-    let parts = line.split(',').map(|s| hash_str(s)).sum();
-    parts
+// the initialization sequence, its steps are separated by commas
+pub fn solve1(sequence: &str) -> i64 {
+    sequence.split(',').map(hash_str).sum()
 }
 
 #[derive(Debug, PartialEq)]
@@ -22,15 +23,6 @@ struct Label {
     value: i64,
 }
 
-impl Label {
-    fn from_set(cmd: Command) -> Option<Self> {
-        match cmd {
-            Command::Remove(_) => None,
-            Command::Set(label, value) => Some(Label { label, value }),
-        }
-    }
-}
-
 type Box = Vec<Label>;
 
 fn new_box() -> Box {
@@ -96,18 +88,15 @@ fn calculate_box(abox: &Box, box_no: usize) -> i64 {
     value
 }
 
-pub fn solve2(lines: Vec<String>) -> i64 {
-    let line = &lines[0];
-    let parts: Vec<String> = line.split(',').map(|s| s.to_owned()).collect();
-
+pub fn solve2(sequence: &str) -> i64 {
     let mut boxes = Vec::new();
     for _ in 0..256 {
         let abox = new_box();
         boxes.push(abox);
     }
 
-    for part in parts {
-        let cmd = Command::from_str(&part).unwrap();
+    for step in sequence.split(',') {
+        let cmd = Command::from_str(step).unwrap();
         match cmd {
             Command::Remove(label) => {
                 let box_index = hash_str(&label) as usize;
@@ -129,9 +118,33 @@ pub fn solve2(lines: Vec<String>) -> i64 {
         .sum()
 }
 
+pub struct Day15;
+
+impl Day for Day15 {
+    const DAY: u32 = 15;
+    const VERSION: &'static str = "1";
+    // the sequence is the first line
+    type Input = String;
+    type Output1 = i64;
+    type Output2 = i64;
+
+    fn parse(input: &str) -> String {
+        input.lines().next().unwrap_or_default().to_string()
+    }
+
+    fn part1(sequence: &String) -> i64 {
+        solve1(sequence)
+    }
+
+    fn part2(sequence: &String) -> i64 {
+        solve2(sequence)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use advent_of_code_2023::solution_lines;
+    use advent_of_code_2023::day::Day;
+    use advent_of_code_2023::solution_parse_str;
 
     use super::*;
 
@@ -142,26 +155,26 @@ mod tests {
 
     #[test]
     fn test_solve1_test() {
-        let result = solution_lines("day15_test", solve1, 1320);
-        assert!(result)
+        let result = solution_parse_str("day15_test", "1", Day15::parse, Day15::part1);
+        assert!(result.is_correct(), "{}", result);
     }
 
     #[test]
     fn test_solve1() {
-        let result = solution_lines("day15", solve1, 510801);
-        assert!(result)
+        let result = solution_parse_str("day15", "1", Day15::parse, Day15::part1);
+        assert!(result.is_correct(), "{}", result);
     }
 
     #[test]
     fn test_solve2() {
-        let result = solution_lines("day15", solve2, 212763);
-        assert!(result)
+        let result = solution_parse_str("day15", "2", Day15::parse, Day15::part2);
+        assert!(result.is_correct(), "{}", result);
     }
 
     #[test]
     fn test_solve2_test() {
-        let result = solution_lines("day15_test", solve2, 145);
-        assert!(result)
+        let result = solution_parse_str("day15_test", "2", Day15::parse, Day15::part2);
+        assert!(result.is_correct(), "{}", result);
     }
 
     #[test]
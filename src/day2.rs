@@ -1,10 +1,12 @@
 // day2
 
+use advent_of_code_2023::day::Day;
+
 fn parse_line(line: &str) -> Game {
     let game_line = line.split(": ").collect::<Vec<&str>>();
     let game_split = game_line[0].split(" ").collect::<Vec<&str>>();
-    let game_no = game_split[1].parse::<i32>().expect(
-        format!("Should be able to parse {} as i32", game_split[1]).as_str()
+    let game_no = game_split[1].parse::<i32>().unwrap_or_else(|_|
+        panic!("Should be able to parse {} as i32", game_split[1])
     );
 
     let mut blue = 0;
@@ -16,8 +18,8 @@ fn parse_line(line: &str) -> Game {
         let draw_split = draw.split(", ").collect::<Vec<&str>>();
         for cube in draw_split {
             let cube_split = cube.split(" ").collect::<Vec<&str>>();
-            let cube_count = cube_split[0].parse::<i32>().expect(
-                format!("Should be able to parse {} as i32", cube_split[0]).as_str()
+            let cube_count = cube_split[0].parse::<i32>().unwrap_or_else(|_|
+                panic!("Should be able to parse {} as i32", cube_split[0])
             );
             let cube_color = cube_split[1];
             match cube_color {
@@ -32,10 +34,9 @@ fn parse_line(line: &str) -> Game {
     Game { game_no, blue, green, red }
 }
 
-pub fn solve1(input: Vec<String>) -> i32 {
+pub fn solve1(games: &[Game]) -> i32 {
     let mut sum = 0;
-    for line in input {
-        let game = parse_line(&line);
+    for game in games {
         if game.red <= 12 && game.green <= 13 && game.blue <= 14 {
             sum += game.game_no
         }
@@ -43,10 +44,9 @@ pub fn solve1(input: Vec<String>) -> i32 {
     sum
 }
 
-pub fn solve2(input: Vec<String>) -> i32 {
+pub fn solve2(games: &[Game]) -> i32 {
     let mut sum = 0;
-    for line in input {
-        let game = parse_line(&line);
+    for game in games {
         let power = game.red * game.green * game.blue;
         sum += power
     }
@@ -54,18 +54,41 @@ pub fn solve2(input: Vec<String>) -> i32 {
 }
 
 #[derive(Debug, PartialEq)]
-struct Game {
+pub struct Game {
     game_no: i32,
     blue: i32,
     green: i32,
     red: i32,
 }
 
+pub struct Day2;
+
+impl Day for Day2 {
+    const DAY: u32 = 2;
+    const VERSION: &'static str = "1";
+    type Input = Vec<Game>;
+    type Output1 = i32;
+    type Output2 = i32;
+
+    fn parse(input: &str) -> Vec<Game> {
+        input.lines().map(parse_line).collect()
+    }
+
+    fn part1(games: &Vec<Game>) -> i32 {
+        solve1(games)
+    }
+
+    fn part2(games: &Vec<Game>) -> i32 {
+        solve2(games)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use advent_of_code_2023::solution_lines;
+    use advent_of_code_2023::day::Day;
+    use advent_of_code_2023::solution_parse_str;
 
-    use crate::day2::Game;
+    use crate::day2::{Day2, Game};
 
     #[test]
     fn test_parse_line() {
@@ -75,25 +98,25 @@ mod tests {
 
     #[test]
     fn test_solve1_test() {
-        let result = solution_lines("day2_test", super::solve1, 8);
-        assert!(result)
+        let result = solution_parse_str("day2_test", "1", Day2::parse, Day2::part1);
+        assert!(result.is_correct(), "{}", result);
     }
 
     #[test]
     fn test_solve1() {
-        let result = solution_lines("day2", super::solve1, 2101);
-        assert!(result)
+        let result = solution_parse_str("day2", "1", Day2::parse, Day2::part1);
+        assert!(result.is_correct(), "{}", result);
     }
 
     #[test]
     fn test_solve2_test() {
-        let result = solution_lines("day2_test", super::solve2, 2286);
-        assert!(result)
+        let result = solution_parse_str("day2_test", "2", Day2::parse, Day2::part2);
+        assert!(result.is_correct(), "{}", result);
     }
 
     #[test]
     fn test_solve2() {
-        let result = solution_lines("day2", super::solve2, 58269);
-        assert!(result)
+        let result = solution_parse_str("day2", "2", Day2::parse, Day2::part2);
+        assert!(result.is_correct(), "{}", result);
     }
 }
@@ -1,5 +1,6 @@
 // day 3
 
+use advent_of_code_2023::day::Day;
 use lazy_static::lazy_static;
 use regex::Regex;
 
@@ -23,35 +24,32 @@ fn extract_numbers_line(line: &str, line_no: i32) -> Vec<Number> {
 fn extract_symbols_line(line: &str, line_no: i32) -> Vec<Symbol> {
     REGEX_SYMBOL
         .find_iter(line)
-        .enumerate()
-        .map(|(_, i)| Symbol { line_no, pos: i.start() as i32 })
+        .map(|i| Symbol { line_no, pos: i.start() as i32 })
         .collect()
 }
 
 fn extract_gears_line(line: &str, line_no: i32) -> Vec<Symbol> {
     REGEX_GEAR
         .find_iter(line)
-        .enumerate()
-        .map(|(_, i)| Symbol { line_no, pos: i.start() as i32 })
+        .map(|i| Symbol { line_no, pos: i.start() as i32 })
         .collect()
 }
 
-fn extract_lines(lines: &Vec<String>) -> (Vec<Number>, Vec<Symbol>) {
-    (lines.iter().enumerate()
+fn extract_lines(input: &str) -> (Vec<Number>, Vec<Symbol>) {
+    (input.lines().enumerate()
          .flat_map(|(line_no, line)| extract_numbers_line(line, line_no as i32))
          .collect(),
-     lines.iter().enumerate()
+     input.lines().enumerate()
          .flat_map(|(line_no, line)| extract_symbols_line(line, line_no as i32))
          .collect())
 }
 
-fn extract_gears(lines: &Vec<String>) -> (Vec<Number>, Vec<Symbol>) {
-    (lines.iter().enumerate()
-         .flat_map(|(line_no, line)| extract_numbers_line(line, line_no as i32))
-         .collect(),
-     lines.iter().enumerate()
-         .flat_map(|(line_no, line)| extract_gears_line(line, line_no as i32))
-         .collect())
+fn extract_schematic(input: &str) -> Schematic {
+    let (numbers, symbols) = extract_lines(input);
+    let gears = input.lines().enumerate()
+        .flat_map(|(line_no, line)| extract_gears_line(line, line_no as i32))
+        .collect();
+    Schematic { numbers, symbols, gears }
 }
 
 fn check_part_number(num: &Number, symbol: &Symbol) -> bool {
@@ -59,13 +57,11 @@ fn check_part_number(num: &Number, symbol: &Symbol) -> bool {
         && symbol.pos >= num.start - 1 && symbol.pos <= num.end
 }
 
-pub fn solve1(input: Vec<String>) -> i32 {
-    let (numbers, symbols) = extract_lines(&input);
-
+pub fn solve1(schematic: &Schematic) -> i32 {
     let mut sum = 0;
-    for number in numbers {
+    for number in &schematic.numbers {
         let adjacent =
-            symbols.iter().any(|symbol| check_part_number(&number, symbol));
+            schematic.symbols.iter().any(|symbol| check_part_number(number, symbol));
         if adjacent {
             sum += number.value
         }
@@ -74,13 +70,12 @@ pub fn solve1(input: Vec<String>) -> i32 {
     sum
 }
 
-pub fn solve2(input: Vec<String>) -> i32 {
-    let (numbers, gears) = extract_gears(&input);
+pub fn solve2(schematic: &Schematic) -> i32 {
     let mut sum = 0;
 
-    for gear in gears {
-        let adjacent: Vec<Number> = numbers.iter()
-            .filter(|number| check_part_number(number, &gear)).cloned().collect();
+    for gear in &schematic.gears {
+        let adjacent: Vec<&Number> = schematic.numbers.iter()
+            .filter(|number| check_part_number(number, gear)).collect();
         if adjacent.len() == 2 {
             sum += adjacent[0].value * adjacent[1].value
         }
@@ -103,11 +98,41 @@ struct Symbol {
     pos: i32,
 }
 
+// the gears are the * symbols, they are among the symbols as well
+pub struct Schematic {
+    numbers: Vec<Number>,
+    symbols: Vec<Symbol>,
+    gears: Vec<Symbol>,
+}
+
+pub struct Day3;
+
+impl Day for Day3 {
+    const DAY: u32 = 3;
+    const VERSION: &'static str = "1";
+    type Input = Schematic;
+    type Output1 = i32;
+    type Output2 = i32;
+
+    fn parse(input: &str) -> Schematic {
+        extract_schematic(input)
+    }
+
+    fn part1(schematic: &Schematic) -> i32 {
+        solve1(schematic)
+    }
+
+    fn part2(schematic: &Schematic) -> i32 {
+        solve2(schematic)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use advent_of_code_2023::solution_lines;
+    use advent_of_code_2023::day::Day;
+    use advent_of_code_2023::solution_parse_str;
 
-    use crate::day3::{extract_gears_line, extract_lines, extract_numbers_line, extract_symbols_line, Number, solve1, solve2, Symbol};
+    use crate::day3::{extract_gears_line, extract_lines, extract_numbers_line, extract_symbols_line, Number, Day3, Symbol};
 
     #[test]
     fn test_extract_line() {
@@ -134,12 +159,8 @@ mod tests {
 
     #[test]
     fn test_extract_lines() {
-        let input = vec![
-            String::from("467..114.."),
-            String::from("...*......"),
-            String::from("..35..633."),
-        ];
-        let result = extract_lines(&input);
+        let input = "467..114..\n...*......\n..35..633.\n";
+        let result = extract_lines(input);
 
         assert_eq!(result.0.len(), 4);
         assert_eq!(result.0[2].value, 35);
@@ -152,25 +173,25 @@ mod tests {
 
     #[test]
     fn test_solve1_test() {
-        let result = solution_lines("day3_test", solve1, 4361);
-        assert!(result)
+        let result = solution_parse_str("day3_test", "1", Day3::parse, Day3::part1);
+        assert!(result.is_correct(), "{}", result);
     }
 
     #[test]
     fn test_solve1() {
-        let result = solution_lines("day3", solve1, 553079);
-        assert!(result)
+        let result = solution_parse_str("day3", "1", Day3::parse, Day3::part1);
+        assert!(result.is_correct(), "{}", result);
     }
 
     #[test]
     fn test_solve2_test() {
-        let result = solution_lines("day3_test", solve2, 467835);
-        assert!(result)
+        let result = solution_parse_str("day3_test", "2", Day3::parse, Day3::part2);
+        assert!(result.is_correct(), "{}", result);
     }
 
     #[test]
     fn test_solve2() {
-        let result = solution_lines("day3", solve2, 84363105);
-        assert!(result)
+        let result = solution_parse_str("day3", "2", Day3::parse, Day3::part2);
+        assert!(result.is_correct(), "{}", result);
     }
 }
@@ -1,5 +1,6 @@
 // day 4
 
+use advent_of_code_2023::day::Day;
 use advent_of_code_2023::Parse;
 
 fn count_winnings(line: &str) -> i32 {
@@ -15,21 +16,19 @@ fn count_winnings(line: &str) -> i32 {
     count as i32
 }
 
-pub fn solve1(lines: Vec<String>) -> i32 {
-    lines.iter()
-        .map(|line| count_winnings(line))
-        .map(|score| if score > 0 { 1 << (score - 1) } else { 0 })
+pub fn solve1(winnings: &[i32]) -> i32 {
+    winnings.iter()
+        .map(|&score| if score > 0 { 1 << (score - 1) } else { 0 })
         .sum()
 }
 
-pub fn solve2(lines: Vec<String>) -> i32 {
-    let mut count_lines: Vec<i32> = vec![1; lines.len()];
+pub fn solve2(winnings: &[i32]) -> i32 {
+    let mut count_lines: Vec<i32> = vec![1; winnings.len()];
 
-    for (index, line) in lines.iter().enumerate() {
-        let count = count_winnings(&line);
+    for (index, &count) in winnings.iter().enumerate() {
         for i in 1..=count {
             let pos = index + i as usize;
-            if pos < lines.len() {
+            if pos < winnings.len() {
                 count_lines[pos] += count_lines[index];
             }
         }
@@ -38,11 +37,35 @@ pub fn solve2(lines: Vec<String>) -> i32 {
 }
 
 
+pub struct Day4;
+
+impl Day for Day4 {
+    const DAY: u32 = 4;
+    const VERSION: &'static str = "1";
+    // the number of winning numbers on each card
+    type Input = Vec<i32>;
+    type Output1 = i32;
+    type Output2 = i32;
+
+    fn parse(input: &str) -> Vec<i32> {
+        input.lines().map(count_winnings).collect()
+    }
+
+    fn part1(winnings: &Vec<i32>) -> i32 {
+        solve1(winnings)
+    }
+
+    fn part2(winnings: &Vec<i32>) -> i32 {
+        solve2(winnings)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use advent_of_code_2023::solution_lines;
+    use advent_of_code_2023::day::Day;
+    use advent_of_code_2023::solution_parse_str;
 
-    use crate::day4::{count_winnings, solve1, solve2};
+    use crate::day4::{count_winnings, Day4};
 
     #[test]
     fn test_count_winnings() {
@@ -52,25 +75,25 @@ mod tests {
 
     #[test]
     fn test_solve1_test() {
-        let result = solution_lines("day4_test", solve1, 13);
-        assert!(result)
+        let result = solution_parse_str("day4_test", "1", Day4::parse, Day4::part1);
+        assert!(result.is_correct(), "{}", result);
     }
 
     #[test]
     fn test_solve1() {
-        let result = solution_lines("day4", solve1, 23028);
-        assert!(result)
+        let result = solution_parse_str("day4", "1", Day4::parse, Day4::part1);
+        assert!(result.is_correct(), "{}", result);
     }
 
     #[test]
     fn test_solve2_test() {
-        let result = solution_lines("day4_test", solve2, 30);
-        assert!(result)
+        let result = solution_parse_str("day4_test", "2", Day4::parse, Day4::part2);
+        assert!(result.is_correct(), "{}", result);
     }
 
     #[test]
     fn test_solve2() {
-        let result = solution_lines("day4", solve2, 9236992);
-        assert!(result)
+        let result = solution_parse_str("day4", "2", Day4::parse, Day4::part2);
+        assert!(result.is_correct(), "{}", result);
     }
 }
@@ -1,13 +1,15 @@
 // day 5
 
+use advent_of_code_2023::day::Day;
+
 #[derive(Debug, PartialEq, Clone)]
-struct Mapping {
+pub struct Mapping {
     offset: i64,
     begin: i64,
     end: i64,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct Error {
     message: String,
 }
@@ -40,78 +42,72 @@ fn parse_mapping_line(line: &str) -> Result<Mapping, Error> {
     })
 }
 
-fn parse_mappings(input: &Vec<String>) -> Result<(Vec<String>, Vec<Mapping>), Error> {
-    let title = input[0].clone();
-    let mut input = input[1..].to_vec();
+fn parse_mappings<'a, 'b>(input: &'a [&'b str]) -> Result<(&'a [&'b str], Vec<Mapping>), Error> {
+    let title = input[0];
+    let mut input = &input[1..];
 
     if !title.ends_with("map:") {
-        return Err(Error::from(&title));
+        return Err(Error::from(title));
     }
     let mut mappings: Vec<Mapping> = Vec::new();
 
     while !input.is_empty() && !input[0].is_empty() {
-        let line = input[0].clone();
-        input = input[1..].to_vec();
-        let mapping = parse_mapping_line(&line)?;
+        let line = input[0];
+        input = &input[1..];
+        let mapping = parse_mapping_line(line)?;
         mappings.push(mapping);
     }
 
     if input.is_empty() {
         Ok((input, mappings))
     } else {
-        Ok((input[1..].to_vec(), mappings))
+        Ok((&input[1..], mappings))
     }
 }
 
-fn parse_input(input: Vec<String>) -> Result<(Vec<i64>, Vec<Vec<Mapping>>), Error> {
-    let mut input = input;
+fn parse_input(input: &str) -> Result<(Vec<i64>, Vec<Vec<Mapping>>), Error> {
+    let lines: Vec<&str> = input.lines().collect();
     let mut result: Vec<Vec<Mapping>> = Vec::new();
 
-    let seeds = input[0]
+    let seeds = lines[0]
         .split_whitespace()
         .skip(1)
         .map(|x| x.parse().map_err(|_| Error::from(x)))
         .collect::<Result<Vec<i64>, Error>>()?;
 
-    input = input[2..].to_vec();
+    let mut input = &lines[2..];
 
     while !input.is_empty() {
-        let (new_input, mappings) = parse_mappings(&input)?;
-        input = new_input;
+        let (rest, mappings) = parse_mappings(input)?;
+        input = rest;
         result.push(mappings);
     }
 
     Ok((seeds, result))
 }
 
-fn transform(input: i64, mappings: Vec<Mapping>) -> i64 {
-    match mappings
-        .into_iter()
-        .find(|m| input >= m.begin && input < m.end)
-    {
+fn transform(input: i64, mappings: &[Mapping]) -> i64 {
+    match mappings.iter().find(|m| input >= m.begin && input < m.end) {
         Some(mapping) => input + mapping.offset,
         None => input,
     }
 }
 
-fn expand_seeds(seeds: &Vec<i64>) -> Vec<i64> {
-    let seed_ranges: Vec<(i64, i64)> = seeds.chunks(2).map(|chunk| (chunk[0], chunk[1])).collect();
-    seed_ranges
-        .into_iter()
+// lazy, the real input expands to billions of seeds
+fn expand_seeds(seeds: &[i64]) -> impl Iterator<Item = i64> + '_ {
+    seeds
+        .chunks(2)
+        .map(|chunk| (chunk[0], chunk[1]))
         .flat_map(|(begin, end)| begin..(begin + end - 1))
-        .collect()
 }
 
-pub fn solve1(input: Vec<String>) -> Result<i64, Error> {
-    let (seeds, mappings) = parse_input(input)?;
-
+pub fn solve1(seeds: &[i64], mappings: &[Vec<Mapping>]) -> Result<i64, Error> {
     let result = seeds
         .iter()
         .map(|seed| {
             mappings
-                .clone()
-                .into_iter()
-                .fold(*seed, |acc, mappings| transform(acc, mappings.clone()))
+                .iter()
+                .fold(*seed, |acc, mappings| transform(acc, mappings))
         })
         .min();
 
@@ -121,17 +117,12 @@ pub fn solve1(input: Vec<String>) -> Result<i64, Error> {
     }
 }
 
-pub fn solve2(input: Vec<String>) -> Result<i64, Error> {
-    let (seeds, mappings) = parse_input(input)?;
-    let seeds = expand_seeds(&seeds);
-
-    let result = seeds
-        .iter()
+pub fn solve2(seeds: &[i64], mappings: &[Vec<Mapping>]) -> Result<i64, Error> {
+    let result = expand_seeds(seeds)
         .map(|seed| {
             mappings
-                .clone()
-                .into_iter()
-                .fold(*seed, |acc, mappings| transform(acc, mappings.clone()))
+                .iter()
+                .fold(seed, |acc, mappings| transform(acc, mappings))
         })
         .min();
 
@@ -141,34 +132,62 @@ pub fn solve2(input: Vec<String>) -> Result<i64, Error> {
     }
 }
 
+pub struct Day5;
+
+impl Day for Day5 {
+    const DAY: u32 = 5;
+    const VERSION: &'static str = "1";
+    // the seeds and the mappings of every step, an invalid input is the
+    // error of both parts
+    type Input = Result<(Vec<i64>, Vec<Vec<Mapping>>), Error>;
+    type Output1 = Result<i64, Error>;
+    type Output2 = Result<i64, Error>;
+
+    fn parse(input: &str) -> Self::Input {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<i64, Error> {
+        let (seeds, mappings) = input.as_ref().map_err(Error::clone)?;
+        solve1(seeds, mappings)
+    }
+
+    fn part2(input: &Self::Input) -> Result<i64, Error> {
+        let (seeds, mappings) = input.as_ref().map_err(Error::clone)?;
+        solve2(seeds, mappings)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use advent_of_code_2023::solution_lines;
+    use advent_of_code_2023::day::Day;
+    use advent_of_code_2023::solution_parse_str;
 
-    use crate::day5::{parse_mapping_line, solve1, solve2, Mapping};
+    use crate::day5::{parse_mapping_line, Day5, Mapping};
 
     #[test]
     fn test_solve1_test() {
-        let result = solution_lines("day5_test", solve1, Ok(35));
-        assert!(result)
+        let result = solution_parse_str("day5_test", "1", Day5::parse, Day5::part1);
+        assert!(result.is_correct(), "{}", result);
     }
 
     #[test]
     fn test_solve1() {
-        let result = solution_lines("day5", solve1, Ok(331445006));
-        assert!(result)
+        let result = solution_parse_str("day5", "1", Day5::parse, Day5::part1);
+        assert!(result.is_correct(), "{}", result);
     }
 
     #[test]
     fn test_solve2_test() {
-        let result = solution_lines("day5_test", solve2, Ok(46));
-        assert!(result)
+        let result = solution_parse_str("day5_test", "2", Day5::parse, Day5::part2);
+        assert!(result.is_correct(), "{}", result);
     }
 
-    // #[test]
+    #[test]
+    #[ignore = "brute force over all seeds does not finish"]
     fn test_solve2() {
-        let result = solution_lines("day5", solve2, Ok(0));
-        assert!(result)
+        let result = solution_parse_str("day5", "2", Day5::parse, Day5::part2);
+        assert!(result.is_correct(), "{}", result);
     }
 
     #[test]
@@ -199,25 +218,20 @@ mod tests {
     #[test]
     fn test_parse_mappings() {
         let input = vec![
-            "a-to-b map:".to_string(),
-            "0 15 37".to_string(),
-            "1 15 37".to_string(),
-            "2 15 37".to_string(),
-            "".to_string(),
-            "b-to-c map:".to_string(),
-            "0 16 37".to_string(),
-            "1 16 37".to_string(),
-            "2 16 37".to_string(),
+            "a-to-b map:",
+            "0 15 37",
+            "1 15 37",
+            "2 15 37",
+            "",
+            "b-to-c map:",
+            "0 16 37",
+            "1 16 37",
+            "2 16 37",
         ];
         let result = super::parse_mappings(&input).unwrap();
         assert_eq!(
             result.0,
-            vec![
-                "b-to-c map:".to_string(),
-                "0 16 37".to_string(),
-                "1 16 37".to_string(),
-                "2 16 37".to_string(),
-            ]
+            vec!["b-to-c map:", "0 16 37", "1 16 37", "2 16 37"]
         );
         assert_eq!(
             result.1,
@@ -243,19 +257,19 @@ mod tests {
 
     #[test]
     fn test_parse_input() {
-        let input = vec![
-            "seeds: 79 14 55 13".to_string(),
-            "".to_string(),
-            "seed-to-soil map:".to_string(),
-            "50 98 2".to_string(),
-            "52 50 48".to_string(),
-            "".to_string(),
-            "soil-to-fertilizer map:".to_string(),
-            "0 15 37".to_string(),
-            "37 52 2".to_string(),
-            "39 0 15".to_string(),
-            "".to_string(),
-        ];
+        let input = concat!(
+            "seeds: 79 14 55 13\n",
+            "\n",
+            "seed-to-soil map:\n",
+            "50 98 2\n",
+            "52 50 48\n",
+            "\n",
+            "soil-to-fertilizer map:\n",
+            "0 15 37\n",
+            "37 52 2\n",
+            "39 0 15\n",
+            "\n",
+        );
         let (seeds, mappings) = super::parse_input(input).unwrap();
         assert_eq!(seeds, vec![79, 14, 55, 13]);
 
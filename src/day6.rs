@@ -1,8 +1,10 @@
 // day 6
 
+use advent_of_code_2023::day::Day;
+
 fn calc_distance(power: i64, duration: i64) -> i64 {
     // function distance = power * (duration - power)
-    return power * (duration - power);
+    power * (duration - power)
 }
 
 fn find_powers_over_record(distance: i64, record: i64) -> i64 {
@@ -16,58 +18,82 @@ fn find_powers_over_record(distance: i64, record: i64) -> i64 {
     }
 }
 
-pub fn solve1(input: Vec<String>) -> Result<i64, String> {
+fn parse_numbers(line: &str) -> Result<Vec<i64>, String> {
+    line.split_whitespace()
+        .skip(1)
+        .map(|x| {
+            x.parse::<i64>()
+                .map_err(|_| format!("could not parse {}", x))
+        })
+        .collect()
+}
+
+// the distances and the records of the races
+fn parse_races(input: &str) -> Result<(Vec<i64>, Vec<i64>), String> {
+    let input: Vec<&str> = input.lines().collect();
     if input.len() != 2 {
         return Err("input too long or too short".to_string());
     }
+    Ok((parse_numbers(input[0])?, parse_numbers(input[1])?))
+}
 
-    let distance: Vec<i64> = input[0]
-        .split_whitespace()
-        .skip(1)
-        .filter_map(|x| x.parse::<i64>().ok())
-        .collect();
-    let records: Vec<i64> = input[1]
-        .split_whitespace()
-        .skip(1)
-        .filter_map(|x| x.parse::<i64>().ok())
-        .collect();
+// the numbers of all races written without the spaces between them
+fn join_numbers(numbers: &[i64]) -> Option<i64> {
+    numbers
+        .iter()
+        .map(|x| x.to_string())
+        .collect::<String>()
+        .parse::<i64>()
+        .ok()
+}
 
+pub fn solve1(distance: &[i64], records: &[i64]) -> i64 {
     let races = distance.iter().zip(records.iter());
-    let winnings: i64 = races
+    races
         .map(|(d, r)| find_powers_over_record(*d, *r))
-        .product();
+        .product()
+}
+
+pub fn solve2(distance: &[i64], records: &[i64]) -> Result<i64, String> {
+    let distance = join_numbers(distance).ok_or("could not parse distance".to_string())?;
+    let records = join_numbers(records).ok_or("could not parse record".to_string())?;
+
+    let winnings = find_powers_over_record(distance, records);
 
     Ok(winnings)
 }
 
-pub fn solve2(input: Vec<String>) -> Result<i64, String> {
-    if input.len() != 2 {
-        return Err("input too long or too short".to_string());
-    }
+pub struct Day6;
 
-    let distance: i64 = input[0]
-        .split_whitespace()
-        .skip(1)
-        .fold(String::new(), |acc, x| acc + x)
-        .parse::<i64>()
-        .map_err(|_| "could not parse distance".to_string())?;
-    let records: i64 = input[1]
-        .split_whitespace()
-        .skip(1)
-        .fold(String::new(), |acc, x| acc + x)
-        .parse::<i64>()
-        .map_err(|_| "could not parse record".to_string())?;
+impl Day for Day6 {
+    const DAY: u32 = 6;
+    const VERSION: &'static str = "1";
+    // an invalid input is the error of both parts
+    type Input = Result<(Vec<i64>, Vec<i64>), String>;
+    type Output1 = Result<i64, String>;
+    type Output2 = Result<i64, String>;
 
-    let winnings = find_powers_over_record(distance, records);
+    fn parse(input: &str) -> Self::Input {
+        parse_races(input)
+    }
 
-    Ok(winnings)
+    fn part1(input: &Self::Input) -> Result<i64, String> {
+        let (distance, records) = input.as_ref().map_err(String::clone)?;
+        Ok(solve1(distance, records))
+    }
+
+    fn part2(input: &Self::Input) -> Result<i64, String> {
+        let (distance, records) = input.as_ref().map_err(String::clone)?;
+        solve2(distance, records)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use advent_of_code_2023::solution_lines;
+    use advent_of_code_2023::day::Day;
+    use advent_of_code_2023::solution_parse_str;
 
-    use super::{calc_distance, find_powers_over_record};
+    use super::{calc_distance, find_powers_over_record, Day6};
 
     #[test]
     fn test_calc_distance() {
@@ -84,25 +110,25 @@ mod tests {
 
     #[test]
     fn test_solve1_test() {
-        let result = solution_lines("day6_test", super::solve1, Ok(288));
-        assert!(result)
+        let result = solution_parse_str("day6_test", "1", Day6::parse, Day6::part1);
+        assert!(result.is_correct(), "{}", result);
     }
 
     #[test]
     fn test_solve1() {
-        let result = solution_lines("day6", super::solve1, Ok(131376));
-        assert!(result)
+        let result = solution_parse_str("day6", "1", Day6::parse, Day6::part1);
+        assert!(result.is_correct(), "{}", result);
     }
 
     #[test]
     fn test_solve2_test() {
-        let result = solution_lines("day6_test", super::solve2, Ok(71503));
-        assert!(result)
+        let result = solution_parse_str("day6_test", "2", Day6::parse, Day6::part2);
+        assert!(result.is_correct(), "{}", result);
     }
 
     #[test]
     fn test_solve2() {
-        let result = solution_lines("day6", super::solve2, Ok(34123437));
-        assert!(result)
+        let result = solution_parse_str("day6", "2", Day6::parse, Day6::part2);
+        assert!(result.is_correct(), "{}", result);
     }
 }
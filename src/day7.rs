@@ -2,7 +2,9 @@ use std::collections::HashMap;
 
 use lazy_static::lazy_static;
 
-#[derive(Debug, Eq, PartialEq, PartialOrd, Ord)]
+use advent_of_code_2023::day::Day;
+
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord)]
 enum Type {
     FiveOfAKind,
     FourOfAKind,
@@ -13,7 +15,8 @@ enum Type {
     HighCard,
 }
 
-struct Hand {
+#[derive(Clone)]
+pub struct Hand {
     cards: Vec<u8>,
     card_type: Type,
     bid: i64,
@@ -70,7 +73,7 @@ fn cards_to_type(cards: Vec<u8>, with_joker: bool) -> Type {
     let mut card_counts = card_counts_map.values().cloned().collect::<Vec<_>>();
     card_counts.sort();
     card_counts.reverse();
-    card_counts[0] = card_counts[0] + joker_count;
+    card_counts[0] += joker_count;
 
     match card_counts.as_slice() {
         [5] => Type::FiveOfAKind,
@@ -107,12 +110,22 @@ fn line_to_hand(line: &str, with_joker: bool) -> Hand {
     }
 }
 
-fn solve(lines: Vec<String>, with_joker: bool) -> i64 {
-    let mut hands = lines
+// the same hand when J is a joker instead of a jack
+fn with_joker(hand: &Hand) -> Hand {
+    let cards: Vec<u8> = hand
+        .cards
         .iter()
-        .map(|line| line_to_hand(line, with_joker))
-        .collect::<Vec<_>>();
+        .map(|&c| if c == 11u8 { 1u8 } else { c })
+        .collect();
+    let card_type = cards_to_type(cards.clone(), true);
+    Hand {
+        cards,
+        card_type,
+        bid: hand.bid,
+    }
+}
 
+fn solve(mut hands: Vec<Hand>) -> i64 {
     hands.sort_by(|hand1, hand2| match hand1.card_type.cmp(&hand2.card_type) {
         std::cmp::Ordering::Equal => compare_lists(&hand2.cards, &hand1.cards),
         non_equal => non_equal,
@@ -125,17 +138,44 @@ fn solve(lines: Vec<String>, with_joker: bool) -> i64 {
         .fold(0, |acc, (i, hand)| acc + (hand.bid * (i as i64 + 1)))
 }
 
-pub fn solve1(lines: Vec<String>) -> i64 {
-    solve(lines, false)
+pub fn solve1(hands: &[Hand]) -> i64 {
+    solve(hands.to_vec())
+}
+
+pub fn solve2(hands: &[Hand]) -> i64 {
+    solve(hands.iter().map(with_joker).collect())
 }
 
-pub fn solve2(lines: Vec<String>) -> i64 {
-    solve(lines, true)
+pub struct Day7;
+
+impl Day for Day7 {
+    const DAY: u32 = 7;
+    const VERSION: &'static str = "1";
+    // the hands with J as a jack
+    type Input = Vec<Hand>;
+    type Output1 = i64;
+    type Output2 = i64;
+
+    fn parse(input: &str) -> Vec<Hand> {
+        input
+            .lines()
+            .map(|line| line_to_hand(line, false))
+            .collect()
+    }
+
+    fn part1(hands: &Vec<Hand>) -> i64 {
+        solve1(hands)
+    }
+
+    fn part2(hands: &Vec<Hand>) -> i64 {
+        solve2(hands)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use advent_of_code_2023::solution_lines;
+    use advent_of_code_2023::day::Day;
+    use advent_of_code_2023::solution_parse_str;
 
     use super::*;
 
@@ -167,29 +207,34 @@ mod tests {
         assert_eq!(test.cards, vec![5, 3, 14, 4, 11]);
         assert_eq!(test.card_type, Type::HighCard);
         assert_eq!(test.bid, 231);
+
+        let joker = with_joker(&test);
+        assert_eq!(joker.cards, vec![5, 3, 14, 4, 1]);
+        assert_eq!(joker.card_type, Type::OnePair);
+        assert_eq!(joker.card_type, line_to_hand("53A4J 231", true).card_type);
     }
 
     #[test]
     fn test_solve1_test() {
-        let result = solution_lines("day7_test", solve1, 6440);
-        assert!(result)
+        let result = solution_parse_str("day7_test", "1", Day7::parse, Day7::part1);
+        assert!(result.is_correct(), "{}", result);
     }
 
     #[test]
     fn test_solve1() {
-        let result = solution_lines("day7", solve1, 251121738);
-        assert!(result)
+        let result = solution_parse_str("day7", "1", Day7::parse, Day7::part1);
+        assert!(result.is_correct(), "{}", result);
     }
 
     #[test]
     fn test_solve2_test() {
-        let result = solution_lines("day7_test", solve2, 5905);
-        assert!(result)
+        let result = solution_parse_str("day7_test", "2", Day7::parse, Day7::part2);
+        assert!(result.is_correct(), "{}", result);
     }
 
     #[test]
     fn test_solve2() {
-        let result = solution_lines("day7", solve2, 251421071);
-        assert!(result)
+        let result = solution_parse_str("day7", "2", Day7::parse, Day7::part2);
+        assert!(result.is_correct(), "{}", result);
     }
 }
@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use advent_of_code_2023::day::Day;
+
 #[derive(Debug, PartialEq, Clone)]
 struct Nodes {
     left: String,
@@ -17,7 +19,7 @@ impl Nodes {
     }
 }
 
-fn lines_to_nodes_map(lines: &Vec<String>) -> HashMap<String, Nodes> {
+fn lines_to_nodes_map(lines: &[&str]) -> HashMap<String, Nodes> {
     let mut nodes_map = HashMap::new();
     for line in lines {
         let split = line.split(" = ").collect::<Vec<&str>>();
@@ -29,11 +31,25 @@ fn lines_to_nodes_map(lines: &Vec<String>) -> HashMap<String, Nodes> {
     nodes_map
 }
 
+// the directions to take and where they lead from every node
+pub struct Network {
+    pattern: String,
+    nodes_map: HashMap<String, Nodes>,
+}
+
+fn parse_network(input: &str) -> Network {
+    let lines: Vec<&str> = input.lines().collect();
+    Network {
+        pattern: lines[0].to_string(),
+        nodes_map: lines_to_nodes_map(&lines[2..]),
+    }
+}
+
 fn find_start_nodes(nodes_map: &HashMap<String, Nodes>) -> Vec<String> {
     nodes_map
         .keys()
-        .filter(|k| k.ends_with("A"))
-        .map(|k| k.clone())
+        .filter(|k| k.ends_with('A'))
+        .cloned()
         .collect()
 }
 
@@ -46,11 +62,9 @@ fn gcd(a: i64, b: i64) -> i64 {
     }
 }
 
-pub fn solve1(lines: Vec<String>) -> i64 {
-    let mut pattern = lines[0].chars().into_iter().cycle();
-
-    let node_lines = lines[2..].to_vec();
-    let nodes_map = lines_to_nodes_map(&node_lines);
+pub fn solve1(network: &Network) -> i64 {
+    let mut pattern = network.pattern.chars().cycle();
+    let nodes_map = &network.nodes_map;
 
     let mut count = 0;
     let mut node = "AAA".to_string();
@@ -68,14 +82,12 @@ pub fn solve1(lines: Vec<String>) -> i64 {
     count
 }
 
-pub fn solve2(lines: Vec<String>) -> i64 {
-    let p_length = lines[0].len() as i64;
-    let mut pattern = lines[0].chars().into_iter().cycle();
+pub fn solve2(network: &Network) -> i64 {
+    let p_length = network.pattern.len() as i64;
+    let mut pattern = network.pattern.chars().cycle();
+    let nodes_map = &network.nodes_map;
 
-    let node_lines = lines[2..].to_vec();
-    let nodes_map = lines_to_nodes_map(&node_lines);
-
-    let ghosts = find_start_nodes(&nodes_map);
+    let ghosts = find_start_nodes(nodes_map);
     let counts = ghosts
         .iter()
         .map(|g| {
@@ -96,48 +108,68 @@ pub fn solve2(lines: Vec<String>) -> i64 {
         .collect::<Vec<i64>>();
 
     let mut lcm: i64 = counts[0];
-    for i in 1..counts.len() {
-        lcm = lcm * counts[i] / gcd(lcm, counts[i]);
+    for count in &counts[1..] {
+        lcm = lcm * count / gcd(lcm, *count);
+    }
+    lcm
+}
+
+pub struct Day8;
+
+impl Day for Day8 {
+    const DAY: u32 = 8;
+    const VERSION: &'static str = "1";
+    type Input = Network;
+    type Output1 = i64;
+    type Output2 = i64;
+
+    fn parse(input: &str) -> Network {
+        parse_network(input)
+    }
+
+    fn part1(network: &Network) -> i64 {
+        solve1(network)
+    }
+
+    fn part2(network: &Network) -> i64 {
+        solve2(network)
     }
-    return lcm;
 }
 
 #[cfg(test)]
 mod tests {
-    use advent_of_code_2023::solution_lines;
+    use advent_of_code_2023::day::Day;
+    use advent_of_code_2023::solution_parse_str;
 
     use super::*;
 
     #[test]
     fn test_solve1_test() {
-        let result = solution_lines("day8_test", solve1, 6);
-        assert!(result)
+        let result = solution_parse_str("day8_test", "1", Day8::parse, Day8::part1);
+        assert!(result.is_correct(), "{}", result);
     }
 
     #[test]
     fn test_solve1() {
-        let result = solution_lines("day8", solve1, 18023);
-        assert!(result)
+        let result = solution_parse_str("day8", "1", Day8::parse, Day8::part1);
+        assert!(result.is_correct(), "{}", result);
     }
 
     #[test]
     fn test_solve2_test() {
-        let result = solution_lines("day8_test2", solve2, 6);
-        assert!(result)
+        let result = solution_parse_str("day8_test2", "2", Day8::parse, Day8::part2);
+        assert!(result.is_correct(), "{}", result);
     }
 
     #[test]
     fn test_solve2() {
-        let result = solution_lines("day8", solve2, 14449445933179);
-        assert!(result)
+        let result = solution_parse_str("day8", "2", Day8::parse, Day8::part2);
+        assert!(result.is_correct(), "{}", result);
     }
 
     #[test]
     fn test_find_start_nodes() {
-        let lines = vec!["AA = (B, C)", "B = (D, E)", "CA = (F, G)", "D = (H, I)"]
-            .into_iter()
-            .map(|s| s.to_string())
-            .collect();
+        let lines = vec!["AA = (B, C)", "B = (D, E)", "CA = (F, G)", "D = (H, I)"];
 
         let nodes_map = lines_to_nodes_map(&lines);
         let mut start_nodes = find_start_nodes(&nodes_map);
@@ -160,15 +192,12 @@ mod tests {
 
     #[test]
     fn test_lines_to_nodes_map() {
-        let lines = vec!["A = (B, C)", "B = (D, E)", "C = (F, G)", "D = (H, I)"]
-            .into_iter()
-            .map(|s| s.to_string())
-            .collect();
+        let lines = vec!["A = (B, C)", "B = (D, E)", "C = (F, G)", "D = (H, I)"];
 
         let nodes_map = lines_to_nodes_map(&lines);
         assert_eq!(
             nodes_map,
-            vec![
+            [
                 (
                     "A".to_string(),
                     Nodes {
@@ -1,6 +1,7 @@
+use advent_of_code_2023::day::Day;
 use advent_of_code_2023::Parse;
 
-fn find_diffs(input: &Vec<i64>) -> Vec<i64> {
+fn find_diffs(input: &[i64]) -> Vec<i64> {
     let mut diffs = Vec::new();
     for i in 1..input.len() {
         diffs.push(input[i] - input[i - 1]);
@@ -8,7 +9,7 @@ fn find_diffs(input: &Vec<i64>) -> Vec<i64> {
     diffs
 }
 
-fn is_all_zero(diff: &Vec<i64>) -> bool {
+fn is_all_zero(diff: &[i64]) -> bool {
     diff.iter().all(|&x| x == 0)
 }
 
@@ -31,7 +32,7 @@ fn add_numbers_last(mut lines: Vec<Vec<i64>>) -> Vec<Vec<i64>> {
     lines.reverse();
     let mut add: i64 = 0;
     for line in &mut lines {
-        add = line.last().unwrap() + add;
+        add += line.last().unwrap();
         line.push(add);
     }
 
@@ -52,69 +53,86 @@ fn add_numbers_first(mut lines: Vec<Vec<i64>>) -> Vec<Vec<i64>> {
 fn find_next_number(line: &Vec<i64>) -> i64 {
     let diffs = find_all_diffs(line);
     let diffs = add_numbers_last(diffs);
-    diffs.last().unwrap().last().unwrap().clone()
+    *diffs.last().unwrap().last().unwrap()
 }
 
 fn find_first_number(line: &Vec<i64>) -> i64 {
     let diffs = find_all_diffs(line);
     let diffs = add_numbers_first(diffs);
-    diffs.last().unwrap().first().unwrap().clone()
+    *diffs.last().unwrap().first().unwrap()
 }
 
-pub fn solve1(lines: Vec<String>) -> i64 {
-    lines
-        .iter()
+fn parse_lines(input: &str) -> Vec<Vec<i64>> {
+    input
+        .lines()
         .map(|line| {
             line.split_to_numbers()
                 .iter()
                 .map(|&x| x as i64)
                 .collect::<Vec<i64>>()
         })
-        .map(|line| find_next_number(&line))
-        .sum()
+        .collect()
 }
 
-pub fn solve2(lines: Vec<String>) -> i64 {
-    lines
-        .iter()
-        .map(|line| {
-            line.split_to_numbers()
-                .iter()
-                .map(|&x| x as i64)
-                .collect::<Vec<i64>>()
-        })
-        .map(|line| find_first_number(&line))
-        .sum()
+pub fn solve1(lines: &[Vec<i64>]) -> i64 {
+    lines.iter().map(find_next_number).sum()
+}
+
+pub fn solve2(lines: &[Vec<i64>]) -> i64 {
+    lines.iter().map(find_first_number).sum()
+}
+
+pub struct Day9;
+
+impl Day for Day9 {
+    const DAY: u32 = 9;
+    const VERSION: &'static str = "1";
+    type Input = Vec<Vec<i64>>;
+    type Output1 = i64;
+    type Output2 = i64;
+
+    fn parse(input: &str) -> Vec<Vec<i64>> {
+        parse_lines(input)
+    }
+
+    fn part1(lines: &Vec<Vec<i64>>) -> i64 {
+        solve1(lines)
+    }
+
+    fn part2(lines: &Vec<Vec<i64>>) -> i64 {
+        solve2(lines)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use advent_of_code_2023::solution_lines;
+    use advent_of_code_2023::day::Day;
+    use advent_of_code_2023::solution_parse_str;
 
     use super::*;
 
     #[test]
     fn test_solve1_test() {
-        let result = solution_lines("day9_test", solve1, 114);
-        assert!(result)
+        let result = solution_parse_str("day9_test", "1", Day9::parse, Day9::part1);
+        assert!(result.is_correct(), "{}", result);
     }
 
     #[test]
     fn test_solve1() {
-        let result = solution_lines("day9", solve1, 1987402313);
-        assert!(result)
+        let result = solution_parse_str("day9", "1", Day9::parse, Day9::part1);
+        assert!(result.is_correct(), "{}", result);
     }
 
     #[test]
     fn test_solve2_test() {
-        let result = solution_lines("day9_test", solve2, 2);
-        assert!(result)
+        let result = solution_parse_str("day9_test", "2", Day9::parse, Day9::part2);
+        assert!(result.is_correct(), "{}", result);
     }
 
     #[test]
     fn test_solve2() {
-        let result = solution_lines("day9", solve2, 900);
-        assert!(result)
+        let result = solution_parse_str("day9", "2", Day9::parse, Day9::part2);
+        assert!(result.is_correct(), "{}", result);
     }
 
     #[test]
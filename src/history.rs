@@ -0,0 +1,311 @@
+// the solve times of earlier runs, to find days that got slower
+//
+// every run appends one line per input file and part to .history.tsv next
+// to the input directory:
+//
+//   RUN  COMMIT  INPUT  PART  MICROSECONDS
+//
+// RUN is the start of the run in milliseconds since 1970, COMMIT the git
+// commit that was checked out or - outside of a git repository
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::bench::format_duration;
+use crate::input;
+use crate::outcome::{Outcome, Status};
+use crate::read_optional;
+
+// changes of shorter parts are mostly noise
+const MIN_DURATION: Duration = Duration::from_micros(100);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub run: u64,
+    pub commit: String,
+    pub input: String,
+    pub part: String,
+    pub solve: Duration,
+}
+
+pub fn history_file() -> PathBuf {
+    input::input_dir().with_file_name(".history.tsv")
+}
+
+pub fn run_id() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+// the commit of the repository the input directory belongs to
+pub fn current_commit() -> Option<String> {
+    let dir = input::input_dir();
+    git_commit(dir.parent().unwrap_or(Path::new("")))
+}
+
+// the commit HEAD points to, directly or through a branch
+pub fn git_commit(repo: &Path) -> Option<String> {
+    let git = repo.join(".git");
+    let head = std::fs::read_to_string(git.join("HEAD")).ok()?;
+    let head = head.trim();
+    let reference = match head.strip_prefix("ref: ") {
+        Some(reference) => reference,
+        None => return Some(head.to_string()),
+    };
+    if let Ok(commit) = std::fs::read_to_string(git.join(reference)) {
+        return Some(commit.trim().to_string());
+    }
+    // branches that have not been touched for a while are only in packed-refs
+    let packed = std::fs::read_to_string(git.join("packed-refs")).ok()?;
+    packed
+        .lines()
+        .filter_map(|line| line.split_once(' '))
+        .find(|(_, name)| *name == reference)
+        .map(|(commit, _)| commit.to_string())
+}
+
+// the outcomes of one run as history entries, only parts that were solved
+// and gave a value count
+pub fn entries(run: u64, commit: &str, outcomes: &[Outcome]) -> Vec<Entry> {
+    outcomes
+        .iter()
+        .filter(|o| !o.cached)
+        .filter(|o| {
+            matches!(
+                o.status,
+                Status::Correct | Status::Wrong | Status::Unknown | Status::Recorded
+            )
+        })
+        .map(|o| Entry {
+            run,
+            commit: commit.to_string(),
+            input: o.input.clone(),
+            part: o.part.clone(),
+            solve: o.timings.solve,
+        })
+        .collect()
+}
+
+pub fn append(path: &Path, entries: &[Entry]) -> Result<(), String> {
+    let mut content = String::new();
+    for e in entries {
+        content.push_str(&format!(
+            "{}\t{}\t{}\t{}\t{}\n",
+            e.run,
+            e.commit,
+            e.input,
+            e.part,
+            e.solve.as_micros()
+        ));
+    }
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(content.as_bytes()))
+        .map_err(|e| format!("Could not write {}: {}", path.display(), e))
+}
+
+fn parse_line(line: &str) -> Option<Entry> {
+    let fields: Vec<&str> = line.split('\t').collect();
+    match fields.as_slice() {
+        [run, commit, input, part, micros] => Some(Entry {
+            run: run.parse().ok()?,
+            commit: commit.to_string(),
+            input: input.to_string(),
+            part: part.to_string(),
+            solve: Duration::from_micros(micros.parse().ok()?),
+        }),
+        _ => None,
+    }
+}
+
+// broken lines are skipped
+pub fn load(path: &Path) -> Result<Vec<Entry>, String> {
+    Ok(read_optional(path)?
+        .lines()
+        .filter_map(parse_line)
+        .collect())
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Change {
+    pub input: String,
+    pub part: String,
+    pub before: Duration,
+    pub after: Duration,
+    // slower by more than the threshold
+    pub regression: bool,
+}
+
+impl Change {
+    pub fn percent(&self) -> f64 {
+        (self.after.as_secs_f64() / self.before.as_secs_f64().max(1e-9) - 1.0) * 100.0
+    }
+}
+
+// compares the latest run with the baseline, which is the latest run of the
+// given commit (a prefix is enough) or else the run before the latest, the
+// two runs need at least one input file and part in common
+pub fn compare(
+    entries: &[Entry],
+    baseline: Option<&str>,
+    threshold: f64,
+) -> Result<Vec<Change>, String> {
+    let mut runs: Vec<(u64, &str)> = entries.iter().map(|e| (e.run, e.commit.as_str())).collect();
+    runs.sort();
+    runs.dedup();
+    let (latest, _) = *runs.last().ok_or("there is no run in the history yet")?;
+    let earlier = &runs[..runs.len() - 1];
+    let base = match baseline {
+        Some(commit) => earlier
+            .iter()
+            .rev()
+            .find(|(_, c)| c.starts_with(commit))
+            .ok_or(format!("there is no earlier run of commit {}", commit))?,
+        None => earlier
+            .last()
+            .ok_or("there is only one run in the history")?,
+    };
+
+    let run = |id: u64| entries.iter().filter(move |e| e.run == id);
+    let changes: Vec<Change> = run(latest)
+        .filter_map(|after| {
+            let before = run(base.0).find(|b| b.input == after.input && b.part == after.part)?;
+            let slower =
+                after.solve.as_secs_f64() > before.solve.as_secs_f64() * (1.0 + threshold / 100.0);
+            Some(Change {
+                input: after.input.clone(),
+                part: after.part.clone(),
+                before: before.solve,
+                after: after.solve,
+                regression: slower && after.solve >= MIN_DURATION,
+            })
+        })
+        .collect();
+    if changes.is_empty() {
+        return Err("no parts in common with the baseline".to_string());
+    }
+    Ok(changes)
+}
+
+pub fn describe(change: &Change) -> String {
+    format!(
+        "{} part {}: {} -> {} ({:+.0}%){}",
+        change.input,
+        change.part,
+        format_duration(change.before),
+        format_duration(change.after),
+        change.percent(),
+        if change.regression { " slower" } else { "" }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::temp_path;
+
+    fn entry(run: u64, commit: &str, input: &str, part: &str, micros: u64) -> Entry {
+        Entry {
+            run,
+            commit: commit.to_string(),
+            input: input.to_string(),
+            part: part.to_string(),
+            solve: Duration::from_micros(micros),
+        }
+    }
+
+    #[test]
+    fn test_append_and_load() {
+        let path = temp_path("history.tsv");
+        let entries = vec![
+            entry(1, "abc", "day8", "1", 5440),
+            entry(1, "abc", "day8", "2", 11550),
+        ];
+        append(&path, &entries).unwrap();
+        append(&path, &entries[..1]).unwrap();
+        std::fs::write(
+            &path,
+            std::fs::read_to_string(&path).unwrap() + "broken line\n",
+        )
+        .unwrap();
+
+        let loaded = load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.len(), 3);
+        assert_eq!(loaded[1], entries[1]);
+    }
+
+    #[test]
+    fn test_compare_with_previous_run() {
+        let entries = vec![
+            entry(1, "abc", "day8", "1", 5000),
+            entry(1, "abc", "day8", "2", 10000),
+            entry(2, "def", "day8", "1", 5100),
+            entry(2, "def", "day8", "2", 15000),
+        ];
+        let changes = compare(&entries, None, 10.0).unwrap();
+        assert_eq!(changes.len(), 2);
+        assert!(!changes[0].regression);
+        assert!(changes[1].regression);
+        assert_eq!(
+            describe(&changes[1]),
+            "day8 part 2: 10.00ms -> 15.00ms (+50%) slower"
+        );
+    }
+
+    #[test]
+    fn test_compare_with_commit() {
+        let entries = vec![
+            entry(1, "abc", "day8", "1", 5000),
+            entry(2, "def", "day8", "1", 9000),
+            entry(3, "def", "day8", "1", 9000),
+        ];
+        let changes = compare(&entries, Some("ab"), 10.0).unwrap();
+        assert!(changes[0].regression);
+        assert_eq!(
+            compare(&entries, Some("xyz"), 10.0).unwrap_err(),
+            "there is no earlier run of commit xyz"
+        );
+        assert_eq!(
+            compare(&[], None, 10.0).unwrap_err(),
+            "there is no run in the history yet"
+        );
+    }
+
+    #[test]
+    fn test_compare_without_common_parts() {
+        let entries = vec![
+            entry(1, "abc", "day5", "1", 5000),
+            entry(2, "abc", "day7", "1", 5000),
+            entry(2, "abc", "day7", "2", 5000),
+        ];
+        assert_eq!(
+            compare(&entries, None, 10.0).unwrap_err(),
+            "no parts in common with the baseline"
+        );
+    }
+
+    #[test]
+    fn test_short_parts_are_not_regressions() {
+        let entries = vec![
+            entry(1, "abc", "day6", "1", 5),
+            entry(2, "abc", "day6", "1", 50),
+        ];
+        assert!(!compare(&entries, None, 10.0).unwrap()[0].regression);
+    }
+
+    #[test]
+    fn test_git_commit() {
+        let repo = Path::new(env!("CARGO_MANIFEST_DIR"));
+        if repo.join(".git").exists() {
+            assert_eq!(git_commit(repo).map(|c| c.len()), Some(40));
+        }
+        assert_eq!(git_commit(&std::env::temp_dir().join("no_repo_here")), None);
+    }
+}
@@ -0,0 +1,149 @@
+// where the puzzle input comes from
+//
+// input files are looked up by name in the input directory, the runner can
+// replace that with a file anywhere or with stdin
+//
+// every user can have their own input files and answers in input/<user>/,
+// their input files are named like alice/day7
+
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Source {
+    File(PathBuf),
+    Stdin,
+}
+
+impl Source {
+    // `-` means stdin, everything else is a path
+    pub fn parse(value: &str) -> Source {
+        match value {
+            "-" => Source::Stdin,
+            path => Source::File(PathBuf::from(path)),
+        }
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            Source::File(path) => path.display().to_string(),
+            Source::Stdin => "stdin".to_string(),
+        }
+    }
+}
+
+// stdin can only be read once
+static STDIN: OnceLock<Result<String, String>> = OnceLock::new();
+
+// input/ in the working directory if there is one, otherwise the one of
+// this crate, so the binary also finds its input when started elsewhere
+pub fn input_dir() -> PathBuf {
+    let local = Path::new("input");
+    if local.is_dir() {
+        local.to_path_buf()
+    } else {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("input")
+    }
+}
+
+// the path of an input file, e.g. input/day8_test2.txt
+pub fn path(input_file: &str) -> PathBuf {
+    input_dir().join(format!("{}.txt", input_file))
+}
+
+// the input files of a day in the input directory, the real one and the
+// test ones, e.g. day8, day8_test and day8_test2 for day8
+pub fn day_inputs(name: &str) -> Vec<String> {
+    let mut inputs: Vec<String> = match std::fs::read_dir(input_dir()) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .filter_map(|file| file.strip_suffix(".txt").map(|stem| stem.to_string()))
+            .filter(|stem| stem == name || stem.starts_with(&format!("{}_", name)))
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    inputs.sort();
+    inputs
+}
+
+// the users with their own input directory, sorted by name
+pub fn users() -> Vec<String> {
+    let mut users: Vec<String> = match std::fs::read_dir(input_dir()) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().is_dir())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .filter(|name| !name.starts_with('.'))
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    users.sort();
+    users
+}
+
+// the input file of a user, e.g. alice/day7, or the shared one without user
+pub fn user_input(user: Option<&str>, input_file: &str) -> String {
+    match user {
+        Some(user) => format!("{}/{}", user, input_file),
+        None => input_file.to_string(),
+    }
+}
+
+// splits alice/day7 into the user and day7
+pub fn split_user(input_file: &str) -> (Option<&str>, &str) {
+    match input_file.rsplit_once('/') {
+        Some((user, name)) => (Some(user), name),
+        None => (None, input_file),
+    }
+}
+
+pub(crate) fn read_stdin() -> Result<String, String> {
+    STDIN
+        .get_or_init(|| {
+            let mut content = String::new();
+            std::io::stdin()
+                .read_to_string(&mut content)
+                .map(|_| content)
+                .map_err(|e| format!("Could not read stdin: {}", e))
+        })
+        .clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Source::parse("-"), Source::Stdin);
+        assert_eq!(
+            Source::parse("/tmp/day7.txt"),
+            Source::File(PathBuf::from("/tmp/day7.txt"))
+        );
+        assert_eq!(Source::parse("-").name(), "stdin");
+    }
+
+    #[test]
+    fn test_user_input() {
+        assert_eq!(user_input(Some("alice"), "day7"), "alice/day7");
+        assert_eq!(user_input(None, "day7"), "day7");
+        assert_eq!(split_user("alice/day7"), (Some("alice"), "day7"));
+        assert_eq!(split_user("day7_test"), (None, "day7_test"));
+        assert!(path("alice/day7").ends_with("input/alice/day7.txt"));
+    }
+
+    #[test]
+    fn test_day_inputs() {
+        assert_eq!(day_inputs("day8"), vec!["day8", "day8_test", "day8_test2"]);
+        assert_eq!(day_inputs("day1"), vec!["day1", "day1_test", "day1_test2"]);
+        assert!(day_inputs("day25").is_empty());
+    }
+
+    #[test]
+    fn test_path() {
+        assert!(path("day1").ends_with("input/day1.txt"));
+        assert!(path("day1").exists());
+    }
+}
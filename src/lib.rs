@@ -1,80 +1,550 @@
-use std::fmt::Debug;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-pub fn solution<I, O>(
+use answer::Answer;
+use bench::format_duration;
+use config::Config;
+use input::Source;
+use memory::Allocations;
+use outcome::{Memory, Outcome, Status, Timings};
+use watchdog::Failure;
+
+pub mod answer;
+pub mod answers;
+pub mod bench;
+pub mod cache;
+pub mod cli;
+pub mod client;
+pub mod config;
+pub mod day;
+pub mod history;
+pub mod input;
+pub mod lint;
+pub mod memory;
+pub mod outcome;
+pub mod pool;
+pub mod readme;
+pub mod report;
+pub mod scaffold;
+pub mod submit;
+#[cfg(test)]
+mod testing;
+pub mod watch;
+pub mod watchdog;
+
+fn read_file(path: &Path) -> Result<String, (Status, String)> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => Ok(content),
+        Err(e) if e.kind() == std::io::ErrorKind::InvalidData => Err((
+            Status::ParseError,
+            format!("Input file {} is not valid UTF-8", path.display()),
+        )),
+        Err(_) => Err((
+            Status::MissingInput,
+            format!("Could not read input file {}", path.display()),
+        )),
+    }
+}
+
+// the content of a file the runner keeps for itself, e.g. the answers or the
+// cache, a missing file is the same as an empty one
+pub fn read_optional(path: &Path) -> Result<String, String> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => Ok(content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+        Err(e) => Err(format!("Could not read {}: {}", path.display(), e)),
+    }
+}
+
+// the content of the input file, or of the source in the config, and the
+// warnings about the input, see src/lint.rs
+pub(crate) fn read_input(
     input_file: &str,
-    parse: fn(Vec<String>) -> I,
-    solve: fn(I) -> O,
-    expected: O,
-) -> bool
+    config: &Config,
+) -> Result<(String, Vec<String>), (Status, String)> {
+    let (name, file_content) = match &config.source {
+        Some(Source::Stdin) => {
+            let content = input::read_stdin().map_err(|message| (Status::MissingInput, message))?;
+            (Source::Stdin.name(), content)
+        }
+        Some(Source::File(path)) => (path.display().to_string(), read_file(path)?),
+        None => {
+            let path = input::path(input_file);
+            (path.display().to_string(), read_file(&path)?)
+        }
+    };
+    Ok(lint::lint(&name, file_content, config.normalize))
+}
+
+// one String per line, for the solvers that take Vec<String>
+fn to_lines(content: &str) -> Vec<String> {
+    content.lines().map(|s| s.to_string()).collect()
+}
+
+// the name outcomes are reported under, the source replaces the input file
+fn input_name(input_file: &str, config: &Config) -> String {
+    match &config.source {
+        Some(source) => source.name(),
+        None => input_file.to_string(),
+    }
+}
+
+// compares the result with the answer stored for this input file and part
+// in input/answers.txt, there are no answers for input from another source
+fn check(
+    input_file: &str,
+    part: &str,
+    result: Answer,
+    timings: Timings,
+    memory: Memory,
+    config: &Config,
+) -> Outcome {
+    let mut outcome = Outcome::new(input_file, part, Status::Unknown);
+    outcome.timings = timings;
+    outcome.memory = memory;
+
+    let known_input = config.source.is_none();
+    let expected = if known_input {
+        answers::expected(input_file, part)
+    } else {
+        Ok(None)
+    };
+    match expected {
+        Ok(Some(expected)) => {
+            outcome.status = if result == expected {
+                Status::Correct
+            } else {
+                Status::Wrong
+            };
+            outcome.expected = Some(expected);
+        }
+        Ok(None) if known_input && config.record => {
+            match answers::record(input_file, part, &result) {
+                Ok(()) => outcome.status = Status::Recorded,
+                Err(message) => outcome.message = Some(message),
+            }
+        }
+        Ok(None) => {}
+        Err(message) => outcome.message = Some(message),
+    }
+    outcome.value = Some(result);
+    outcome
+}
+
+// the outcomes of `parts` from the result cache, None unless all of them are
+// cached for the current content of the input file, see src/cache.rs
+pub(crate) fn cached_solution(
+    input_file: &str,
+    day: &str,
+    version: &str,
+    parts: &[&str],
+    config: &Config,
+) -> Option<Vec<Outcome>> {
+    if config.cache != cache::Mode::Use {
+        return None;
+    }
+    let (content, warnings) = read_input(input_file, config).ok()?;
+    let input_hash = cache::hash(&content);
+    let input_file = &input_name(input_file, config);
+    let mut outcomes = Vec::new();
+    for part in parts {
+        let key = cache::Key {
+            day: day.to_string(),
+            version: version.to_string(),
+            input_hash,
+            part: part.to_string(),
+        };
+        let answer = cache::lookup(&key)?;
+        let mut outcome = check(
+            input_file,
+            part,
+            answer,
+            Timings::default(),
+            Memory::default(),
+            config,
+        );
+        outcome.cached = true;
+        outcomes.push(outcome.with_warnings(warnings.clone()));
+    }
+    Some(outcomes)
+}
+
+// stores the answers of the outcomes in the result cache, parts that did not
+// give one are left out
+pub(crate) fn cache_solution(
+    input_file: &str,
+    day: &str,
+    version: &str,
+    outcomes: &[Outcome],
+    config: &Config,
+) -> Result<(), String> {
+    if config.cache == cache::Mode::Off {
+        return Ok(());
+    }
+    let input_hash = match read_input(input_file, config) {
+        Ok((content, _)) => cache::hash(&content),
+        Err(_) => return Ok(()),
+    };
+    let entries = outcomes
+        .iter()
+        .filter(|o| {
+            matches!(
+                o.status,
+                Status::Correct | Status::Wrong | Status::Unknown | Status::Recorded
+            )
+        })
+        .filter_map(|o| {
+            let key = cache::Key {
+                day: day.to_string(),
+                version: version.to_string(),
+                input_hash,
+                part: o.part.clone(),
+            };
+            Some((key, o.value.clone()?))
+        })
+        .collect();
+    cache::store(entries)
+}
+
+// the outcome when parse or solve did not return a value
+fn failed(
+    input_file: &str,
+    part: &str,
+    failure: Failure,
+    parsing: bool,
+    timings: Timings,
+) -> Outcome {
+    let step = if parsing { "parse" } else { "solve" };
+    let (status, message) = match failure {
+        Failure::Panicked(message) if parsing => (Status::ParseError, message),
+        Failure::Panicked(message) => (Status::Panicked, message),
+        Failure::Timeout(timeout) => (
+            Status::Timeout,
+            format!(
+                "{} gave no result within {}",
+                step,
+                format_duration(timeout)
+            ),
+        ),
+    };
+    let mut outcome = Outcome::new(input_file, part, status).with_message(message);
+    outcome.timings = timings;
+    outcome
+}
+
+// runs `solve` on the parsed input under the watchdog, `after_parse` holds
+// read time, parse time and parse allocations
+fn solve_part<I, O>(
+    input_file: &str,
+    part: &str,
+    data_structure: I,
+    solve: impl FnOnce(I) -> O + Send + 'static,
+    after_parse: (Duration, Duration, Option<Allocations>),
+    config: &Config,
+) -> Outcome
+where
+    I: Send + 'static,
+    O: Into<Answer> + Send + 'static,
+{
+    let (read, parse, parse_memory) = after_parse;
+    let start = Instant::now();
+    match watchdog::run(config.timeout, move || {
+        memory::measure(|| solve(data_structure))
+    }) {
+        Ok(((result, solve_memory), solve)) => {
+            let memory = Memory {
+                parse: parse_memory,
+                solve: solve_memory,
+            };
+            check(
+                input_file,
+                part,
+                result.into(),
+                Timings { read, parse, solve },
+                memory,
+                config,
+            )
+        }
+        Err(failure) => {
+            let timings = Timings {
+                read,
+                parse,
+                solve: start.elapsed(),
+            };
+            failed(input_file, part, failure, false, timings)
+        }
+    }
+}
+
+// reads the input file, runs `parse` on its content and `solve` on the
+// result and compares that with the answer stored for this input file and
+// part in input/answers.txt, `parse` and `solve` run under the watchdog, so
+// panics and timeouts end up in the outcome
+pub(crate) fn run_part<I, O>(
+    input_file: &str,
+    part: &str,
+    config: &Config,
+    parse: impl FnOnce(String) -> I + Send + 'static,
+    solve: impl FnOnce(I) -> O + Send + 'static,
+) -> Outcome
 where
-    O: PartialEq + Debug,
+    I: Send + 'static,
+    O: Into<Answer> + Send + 'static,
 {
-    let long_file_name = format!("input/{}.txt", input_file);
-    let file_content = match std::fs::read_to_string(&long_file_name) {
-        Ok(content) => content,
-        Err(_) => {
-            println!("Could not read input file {}", &long_file_name);
-            return false;
+    let input_file = &input_name(input_file, config);
+    let start = Instant::now();
+    let (content, warnings) = match read_input(input_file, config) {
+        Ok(read) => read,
+        Err((status, message)) => {
+            return Outcome::new(input_file, part, status).with_message(message)
         }
     };
+    let read = start.elapsed();
+
+    let start = Instant::now();
+    let parsed = watchdog::run(config.timeout, move || memory::measure(|| parse(content)));
+    let ((data_structure, parse_memory), parse) = match parsed {
+        Ok(result) => result,
+        Err(failure) => {
+            let timings = Timings {
+                read,
+                parse: start.elapsed(),
+                ..Timings::default()
+            };
+            return failed(input_file, part, failure, true, timings).with_warnings(warnings);
+        }
+    };
+
+    solve_part(
+        input_file,
+        part,
+        data_structure,
+        solve,
+        (read, parse, parse_memory),
+        config,
+    )
+    .with_warnings(warnings)
+}
 
-    let strings = file_content
-        .lines()
-        .map(|s| s.to_string())
-        .collect::<Vec<String>>();
+// like run_part, but reads and parses the input file once and runs both parts
+// on it, the parts may return different types
+pub(crate) fn run_both<I, O1, O2>(
+    input_file: &str,
+    config: &Config,
+    parse: impl FnOnce(String) -> I + Send + 'static,
+    solve1: impl FnOnce(I) -> O1 + Send + 'static,
+    solve2: impl FnOnce(I) -> O2 + Send + 'static,
+) -> Vec<Outcome>
+where
+    I: Clone + Send + 'static,
+    O1: Into<Answer> + Send + 'static,
+    O2: Into<Answer> + Send + 'static,
+{
+    let input_file = &input_name(input_file, config);
+    let start = Instant::now();
+    let (content, warnings) = match read_input(input_file, config) {
+        Ok(read) => read,
+        Err((status, message)) => {
+            return vec![
+                Outcome::new(input_file, "1", status).with_message(message.clone()),
+                Outcome::new(input_file, "2", status).with_message(message),
+            ]
+        }
+    };
+    let read = start.elapsed();
 
-    let data_structure = parse(strings);
-    let result = solve(data_structure);
+    let start = Instant::now();
+    let parsed = watchdog::run(config.timeout, move || memory::measure(|| parse(content)));
+    let ((data_structure, parse_memory), parse) = match parsed {
+        Ok(result) => result,
+        Err(failure) => {
+            let timings = Timings {
+                read,
+                parse: start.elapsed(),
+                ..Timings::default()
+            };
+            return vec![
+                failed(input_file, "1", failure.clone(), true, timings.clone())
+                    .with_warnings(warnings.clone()),
+                failed(input_file, "2", failure, true, timings).with_warnings(warnings),
+            ];
+        }
+    };
 
-    if result == expected {
-        println!("{}: Correct Result {:?}", input_file, result);
-        true
+    let data_structure1 = data_structure.clone();
+    let after_parse = (read, parse, parse_memory);
+    let (outcome1, outcome2) = if config.jobs > 1 {
+        std::thread::scope(|scope| {
+            let part1 = scope.spawn(|| {
+                solve_part(
+                    input_file,
+                    "1",
+                    data_structure1,
+                    solve1,
+                    after_parse,
+                    config,
+                )
+            });
+            let outcome2 = solve_part(input_file, "2", data_structure, solve2, after_parse, config);
+            let outcome1 = part1.join().unwrap_or_else(|_| {
+                Outcome::new(input_file, "1", Status::Panicked)
+                    .with_message("part 1 thread panicked".to_string())
+            });
+            (outcome1, outcome2)
+        })
     } else {
-        println!("{}: Expected {:?}, got {:?}", input_file, expected, result);
-        false
-    }
+        (
+            solve_part(
+                input_file,
+                "1",
+                data_structure1,
+                solve1,
+                after_parse,
+                config,
+            ),
+            solve_part(input_file, "2", data_structure, solve2, after_parse, config),
+        )
+    };
+
+    vec![
+        outcome1.with_warnings(warnings.clone()),
+        outcome2.with_warnings(warnings),
+    ]
 }
 
-pub fn solution_lines<O>(input_file: &str, solve: fn(Vec<String>) -> O, expected: O) -> bool
+// runs `solve` on the input file parsed by `parse` from the whole file content,
+// the way a Day is run
+pub fn solution_parse_str<I, O>(
+    input_file: &str,
+    part: &str,
+    parse: fn(&str) -> I,
+    solve: fn(&I) -> O,
+) -> Outcome
 where
-    O: PartialEq + Debug,
+    I: Send + 'static,
+    O: Into<Answer> + Send + 'static,
 {
-    solution(input_file, parse_to_strings, solve, expected)
+    run_part(
+        input_file,
+        part,
+        &Config::default(),
+        move |content| parse(&content),
+        move |input: I| solve(&input),
+    )
 }
 
-pub fn solution_both<I, O>(
+// runs `solve` on the whole file content without copying it
+pub fn solution_str<O>(input_file: &str, part: &str, solve: fn(&str) -> O) -> Outcome
+where
+    O: Into<Answer> + Send + 'static,
+{
+    run_part(
+        input_file,
+        part,
+        &Config::default(),
+        |content| content,
+        move |content: String| solve(&content),
+    )
+}
+
+// runs `solve` on the input file split into lines and parsed by `parse`
+pub fn solution<I, O>(
     input_file: &str,
+    part: &str,
     parse: fn(Vec<String>) -> I,
-    solve1: fn(I) -> O,
-    solve2: fn(I) -> O,
-    expected1: O,
-    expected2: O,
-) where
-    O: PartialEq + Debug,
+    solve: fn(I) -> O,
+) -> Outcome
+where
+    I: Send + 'static,
+    O: Into<Answer> + Send + 'static,
 {
-    let start = std::time::Instant::now();
-    solution(input_file, parse, solve1, expected1);
-    solution(input_file, parse, solve2, expected2);
-    println!("{}: {}ms", input_file, start.elapsed().as_millis());
+    run_part(
+        input_file,
+        part,
+        &Config::default(),
+        move |content| parse(to_lines(&content)),
+        solve,
+    )
 }
 
-pub fn solution_both_lines<O>(
+pub fn solution_lines<O>(input_file: &str, part: &str, solve: fn(Vec<String>) -> O) -> Outcome
+where
+    O: Into<Answer> + Send + 'static,
+{
+    solution(input_file, part, parse_to_strings, solve)
+}
+
+// both parts share the parsed input, it is not copied for each part
+pub fn solution_both_parse_str<I, O1, O2>(
+    input_file: &str,
+    parse: fn(&str) -> I,
+    solve1: fn(&I) -> O1,
+    solve2: fn(&I) -> O2,
+) -> Vec<Outcome>
+where
+    I: Send + Sync + 'static,
+    O1: Into<Answer> + Send + 'static,
+    O2: Into<Answer> + Send + 'static,
+{
+    run_both(
+        input_file,
+        &Config::default(),
+        move |content| Arc::new(parse(&content)),
+        move |input: Arc<I>| solve1(&input),
+        move |input: Arc<I>| solve2(&input),
+    )
+}
+
+// both parts share the file content, it is not copied for each part
+pub fn solution_both_str<O1, O2>(
     input_file: &str,
-    solve1: fn(Vec<String>) -> O,
-    solve2: fn(Vec<String>) -> O,
-    expected1: O,
-    expected2: O,
-) where
-    O: PartialEq + Debug,
+    solve1: fn(&str) -> O1,
+    solve2: fn(&str) -> O2,
+) -> Vec<Outcome>
+where
+    O1: Into<Answer> + Send + 'static,
+    O2: Into<Answer> + Send + 'static,
+{
+    run_both(
+        input_file,
+        &Config::default(),
+        Arc::<str>::from,
+        move |content: Arc<str>| solve1(&content),
+        move |content: Arc<str>| solve2(&content),
+    )
+}
+
+pub fn solution_both<I, O1, O2>(
+    input_file: &str,
+    parse: fn(Vec<String>) -> I,
+    solve1: fn(I) -> O1,
+    solve2: fn(I) -> O2,
+) -> Vec<Outcome>
+where
+    I: Clone + Send + 'static,
+    O1: Into<Answer> + Send + 'static,
+    O2: Into<Answer> + Send + 'static,
 {
-    solution_both(
+    run_both(
         input_file,
-        parse_to_strings,
+        &Config::default(),
+        move |content| parse(to_lines(&content)),
         solve1,
         solve2,
-        expected1,
-        expected2,
-    );
+    )
+}
+
+pub fn solution_both_lines<O1, O2>(
+    input_file: &str,
+    solve1: fn(Vec<String>) -> O1,
+    solve2: fn(Vec<String>) -> O2,
+) -> Vec<Outcome>
+where
+    O1: Into<Answer> + Send + 'static,
+    O2: Into<Answer> + Send + 'static,
+{
+    solution_both(input_file, parse_to_strings, solve1, solve2)
 }
 
 // a function the takes a vector of strings and
@@ -109,7 +579,7 @@ impl Parse for String {
         self.split_whitespace()
             .map(|s| {
                 s.parse::<i32>()
-                    .expect(format!("should be able to convert {} to i32", s).as_str())
+                    .unwrap_or_else(|_| panic!("should be able to convert {} to i32", s))
             })
             .collect::<Vec<i32>>()
     }
@@ -120,7 +590,7 @@ impl Parse for &str {
         self.split_whitespace()
             .map(|s| {
                 s.parse::<i32>()
-                    .expect(format!("should be able to convert {} to i32", s).as_str())
+                    .unwrap_or_else(|_| panic!("should be able to convert {} to i32", s))
             })
             .collect::<Vec<i32>>()
     }
@@ -134,3 +604,101 @@ macro_rules! s {
         s
     }};
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn panic_in_parse(_: Vec<String>) -> Vec<String> {
+        panic!("bad input")
+    }
+
+    fn count(lines: Vec<String>) -> usize {
+        lines.len()
+    }
+
+    fn panic_in_solve(lines: Vec<String>) -> usize {
+        panic!("No solution for {} lines", lines.len())
+    }
+
+    #[test]
+    fn test_solution_catches_panic_in_solve() {
+        let outcome = solution_lines("day1_test", "1", panic_in_solve);
+        assert_eq!(outcome.status, Status::Panicked);
+        assert_eq!(outcome.message.as_deref(), Some("No solution for 4 lines"));
+    }
+
+    #[test]
+    fn test_solution_catches_panic_in_parse() {
+        let outcome = solution("day1_test", "1", panic_in_parse, count);
+        assert_eq!(outcome.status, Status::ParseError);
+        assert_eq!(outcome.message.as_deref(), Some("bad input"));
+    }
+
+    #[test]
+    fn test_read_optional() {
+        let path = testing::temp_path("optional.txt");
+        assert_eq!(read_optional(&path), Ok(String::new()));
+        std::fs::write(&path, "1 = 142\n").unwrap();
+        assert_eq!(read_optional(&path), Ok("1 = 142\n".to_string()));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_check_does_not_record_an_error() {
+        let config = Config {
+            record: true,
+            ..Config::default()
+        };
+        let error = Answer::Error("\"no\"".to_string());
+        let outcome = check(
+            "day1_test",
+            "3",
+            error,
+            Timings::default(),
+            Memory::default(),
+            &config,
+        );
+        assert_eq!(outcome.status, Status::Unknown);
+        assert_eq!(
+            outcome.message.as_deref(),
+            Some("an error is not recorded as the answer")
+        );
+    }
+
+    #[test]
+    fn test_solution_both_runs_part2_after_panic() {
+        let outcomes = solution_both_lines("day1_test", panic_in_solve, count);
+        assert_eq!(outcomes[0].status, Status::Panicked);
+        assert_eq!(outcomes[1].value, Some(Answer::from(4)));
+    }
+
+    fn first_line(input: &str) -> String {
+        input.lines().next().unwrap_or_default().to_string()
+    }
+
+    #[test]
+    fn test_solution_both_with_different_types() {
+        let outcomes = solution_both_str("day1_test", count_str, first_line);
+        assert_eq!(outcomes[0].value, Some(Answer::from(4)));
+        assert_eq!(outcomes[1].value, Some(Answer::from("1abc2")));
+    }
+
+    fn count_str(input: &str) -> usize {
+        input.lines().count()
+    }
+
+    #[test]
+    fn test_solution_str_sees_the_same_lines() {
+        let outcome = solution_str("day1_test", "1", count_str);
+        assert_eq!(outcome.value, solution_lines("day1_test", "1", count).value);
+
+        let length = std::fs::read_to_string("input/day1_test.txt")
+            .unwrap()
+            .len();
+        let outcomes =
+            solution_both_parse_str("day1_test", |input| input.len(), |len| *len, |len| len * 2);
+        assert_eq!(outcomes[0].value, Some(Answer::from(length)));
+        assert_eq!(outcomes[1].value, Some(Answer::from(2 * length)));
+    }
+}
@@ -0,0 +1,216 @@
+// checks of the input before it is split into lines
+//
+// a BOM, CRLF line endings, trailing whitespace, empty lines at the end or
+// non-ASCII characters break parsers that expect the input exactly the way
+// adventofcode.com delivers it, they are reported as warnings and, when
+// normalizing, removed before solving
+
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Issue {
+    Bom,
+    CrLf,
+    TrailingWhitespace,
+    TrailingEmptyLines,
+    NonAscii,
+}
+
+impl Issue {
+    fn describe(&self) -> &'static str {
+        match self {
+            Issue::Bom => "byte order mark",
+            Issue::CrLf => "CRLF line ending",
+            Issue::TrailingWhitespace => "trailing whitespace",
+            Issue::TrailingEmptyLines => "empty line at the end",
+            Issue::NonAscii => "non-ASCII character",
+        }
+    }
+
+    // non-ASCII characters can be part of the puzzle, they are left alone
+    fn normalizable(&self) -> bool {
+        !matches!(self, Issue::NonAscii)
+    }
+}
+
+// one issue with the first line it appears on, counted once per line
+#[derive(Debug, Clone, PartialEq)]
+pub struct Warning {
+    pub issue: Issue,
+    pub line: usize,
+    pub lines: usize,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.issue.describe())?;
+        if self.lines > 1 {
+            write!(f, " ({} lines)", self.lines)?;
+        }
+        Ok(())
+    }
+}
+
+fn add(warnings: &mut Vec<Warning>, issue: Issue, line: usize) {
+    match warnings.iter_mut().find(|w| w.issue == issue) {
+        Some(warning) => warning.lines += 1,
+        None => warnings.push(Warning {
+            issue,
+            line,
+            lines: 1,
+        }),
+    }
+}
+
+pub fn check(content: &str) -> Vec<Warning> {
+    // an empty file, e.g. the input new creates, has no line to warn about
+    if content.is_empty() {
+        return Vec::new();
+    }
+    let mut warnings = Vec::new();
+    if content.starts_with('\u{feff}') {
+        add(&mut warnings, Issue::Bom, 1);
+    }
+    let content = content.trim_start_matches('\u{feff}');
+
+    let lines: Vec<&str> = content.split('\n').collect();
+    // the newline at the end of the last line is expected
+    let last = if content.ends_with('\n') {
+        lines.len() - 1
+    } else {
+        lines.len()
+    };
+    for (index, line) in lines[..last].iter().enumerate() {
+        let number = index + 1;
+        let line = match line.strip_suffix('\r') {
+            Some(line) => {
+                add(&mut warnings, Issue::CrLf, number);
+                line
+            }
+            None => line,
+        };
+        if line.ends_with(|c: char| c.is_whitespace()) {
+            add(&mut warnings, Issue::TrailingWhitespace, number);
+        }
+        if !line.is_ascii() {
+            add(&mut warnings, Issue::NonAscii, number);
+        }
+    }
+
+    let content_lines = lines[..last]
+        .iter()
+        .rposition(|l| !l.trim().is_empty())
+        .map_or(0, |i| i + 1);
+    for number in content_lines + 1..=last {
+        add(&mut warnings, Issue::TrailingEmptyLines, number);
+    }
+    warnings
+}
+
+// removes everything check warns about, except non-ASCII characters
+pub fn normalize(content: &str) -> String {
+    let content = content.trim_start_matches('\u{feff}');
+    let mut lines: Vec<&str> = content.lines().map(|line| line.trim_end()).collect();
+    while lines.last() == Some(&"") {
+        lines.pop();
+    }
+    let mut normalized = lines.join("\n");
+    normalized.push('\n');
+    normalized
+}
+
+// warnings for the input, prefixed with its name, and the content to solve,
+// normalized if `normalize` is set
+pub fn lint(name: &str, content: String, normalize: bool) -> (String, Vec<String>) {
+    let warnings = check(&content);
+    let normalize = normalize && warnings.iter().any(|w| w.issue.normalizable());
+    let messages = warnings
+        .iter()
+        .map(|w| {
+            if normalize && w.issue.normalizable() {
+                format!("{} {}, normalized", name, w)
+            } else {
+                format!("{} {}", name, w)
+            }
+        })
+        .collect();
+    let content = if normalize {
+        self::normalize(&content)
+    } else {
+        content
+    };
+    (content, messages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_clean() {
+        assert_eq!(check("Time: 7 15 30\nDistance: 9 40 200\n"), vec![]);
+        assert_eq!(check("rn=1,cm-"), vec![]);
+        assert_eq!(check(""), vec![]);
+    }
+
+    #[test]
+    fn test_check() {
+        let warnings = check("\u{feff}a \r\nb\r\nc\u{e9}\n\n\n");
+        assert_eq!(
+            warnings,
+            vec![
+                Warning {
+                    issue: Issue::Bom,
+                    line: 1,
+                    lines: 1
+                },
+                Warning {
+                    issue: Issue::CrLf,
+                    line: 1,
+                    lines: 2
+                },
+                Warning {
+                    issue: Issue::TrailingWhitespace,
+                    line: 1,
+                    lines: 1
+                },
+                Warning {
+                    issue: Issue::NonAscii,
+                    line: 3,
+                    lines: 1
+                },
+                Warning {
+                    issue: Issue::TrailingEmptyLines,
+                    line: 4,
+                    lines: 2
+                },
+            ]
+        );
+        assert_eq!(
+            warnings[1].to_string(),
+            "line 1: CRLF line ending (2 lines)"
+        );
+    }
+
+    #[test]
+    fn test_check_tabs() {
+        let warnings = check("1 2\t\n3 4\n");
+        assert_eq!(
+            warnings,
+            vec![Warning {
+                issue: Issue::TrailingWhitespace,
+                line: 1,
+                lines: 1
+            }]
+        );
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(
+            normalize("\u{feff}a \r\nb\r\nc\u{e9}\n\n\n"),
+            "a\nb\nc\u{e9}\n"
+        );
+        assert_eq!(normalize("rn=1,cm-"), "rn=1,cm-\n");
+    }
+}
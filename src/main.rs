@@ -1,4 +1,25 @@
-use advent_of_code_2023::solution_both_lines;
+use advent_of_code_2023::cli::{parse_args, Command, Days, Format, Users, USAGE};
+use std::time::Duration;
+
+use advent_of_code_2023::answer::Answer;
+use advent_of_code_2023::answers;
+use advent_of_code_2023::bench::BenchConfig;
+use advent_of_code_2023::client::{self, Client, Fetched};
+use advent_of_code_2023::config::Config;
+use advent_of_code_2023::day::Registry;
+use advent_of_code_2023::history;
+use advent_of_code_2023::input;
+#[cfg(feature = "count-allocations")]
+use advent_of_code_2023::memory::CountingAllocator;
+use advent_of_code_2023::outcome::{Outcome, Status};
+use advent_of_code_2023::pool;
+use advent_of_code_2023::readme;
+use advent_of_code_2023::report::{
+    to_markdown, JsonReporter, JunitReporter, Reporter, TextReporter,
+};
+use advent_of_code_2023::scaffold::scaffold;
+use advent_of_code_2023::submit::{self, Verdict};
+use advent_of_code_2023::watch::watch;
 
 mod day1;
 mod day10;
@@ -13,22 +34,338 @@ mod day7;
 mod day8;
 mod day9;
 
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+// how long parse and each part may take before the runner moves on,
+// day 5 part 2 brute-forces every seed and never finishes
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+// how much slower than the baseline a part may get before compare flags it
+const DEFAULT_THRESHOLD: f64 = 10.0;
+
+fn registry() -> Registry {
+    let mut registry = Registry::new();
+    registry.register(day1::Day1);
+    registry.register(day2::Day2);
+    registry.register(day3::Day3);
+    registry.register(day4::Day4);
+    registry.register(day5::Day5);
+    registry.register(day6::Day6);
+    registry.register(day7::Day7);
+    registry.register(day8::Day8);
+    registry.register(day9::Day9);
+    registry.register(day10::Day10);
+    registry.register(day11::Day11);
+    registry.register(day15::Day15);
+    registry
+}
+
+// the config of commands that only choose the timeout
+fn with_timeout(timeout: Option<Duration>) -> Config {
+    Config {
+        timeout: Some(timeout.unwrap_or(DEFAULT_TIMEOUT)),
+        ..Config::default()
+    }
+}
+
+// appends the timings of a run to the history, unless they do not tell how
+// fast the days are
+fn add_to_history(run: u64, outcomes: &[Outcome], config: &Config) {
+    // timings of an input given on the command line say nothing about the day
+    if config.source.is_some() {
+        return;
+    }
+    // parts that run at the same time slow each other down
+    if config.jobs > 1 {
+        eprintln!("warning: runs with more than one job are not added to the history");
+        return;
+    }
+    // a part that timed out keeps running and slows down everything after it
+    let timely = outcomes
+        .iter()
+        .take_while(|o| o.status != Status::Timeout)
+        .count();
+    if timely < outcomes.len() {
+        eprintln!("warning: the timings after a timeout are not added to the history");
+    }
+    let commit = history::current_commit();
+    let entries = history::entries(run, commit.as_deref().unwrap_or("-"), &outcomes[..timely]);
+    if let Err(message) = history::append(&history::history_file(), &entries) {
+        eprintln!("warning: {}", message);
+    }
+}
+
+fn bench(registry: &Registry, days: Vec<u32>, part: Option<u8>, config: &BenchConfig) {
+    for day in days {
+        let solver = registry.get(day).unwrap();
+        match solver.bench(part, config) {
+            Some(results) => {
+                for (part, stats) in results {
+                    match stats {
+                        Ok(stats) => println!("{} part {}: {}", solver.name(), part, stats),
+                        Err(message) => println!("{} part {}: {}", solver.name(), part, message),
+                    }
+                }
+            }
+            None => println!("{}: no input to benchmark", solver.name()),
+        }
+    }
+}
+
+// runs a command that parsed fine, errors are not about the usage
+fn execute(registry: &Registry, command: Command) -> Result<(), String> {
+    match command {
+        Command::Run {
+            days,
+            part,
+            record,
+            format,
+            timeout,
+            input,
+            users,
+            normalize,
+            cache,
+            jobs,
+        } => {
+            let days = days.select(&registry.days())?;
+            // when cross-checking, days without input of a user are left out
+            let all_users = users == Users::All;
+            let users = match users {
+                Users::Shared => vec![None],
+                Users::Named(user) if input::users().contains(&user) => vec![Some(user)],
+                Users::Named(user) => return Err(format!("there is no input/{} directory", user)),
+                Users::All => std::iter::once(None)
+                    .chain(input::users().into_iter().map(Some))
+                    .collect(),
+            };
+            let defaults = with_timeout(timeout);
+            let config = Config {
+                record: record || defaults.record,
+                source: input,
+                normalize,
+                cache,
+                jobs: jobs.unwrap_or(1),
+                ..defaults
+            };
+            let mut reporter: Box<dyn Reporter> = match format {
+                Format::Text => Box::new(TextReporter),
+                Format::Json => Box::new(JsonReporter::default()),
+                Format::Junit => Box::new(JunitReporter::default()),
+            };
+            let run = history::run_id();
+            let mut tasks = Vec::new();
+            for day in days {
+                let solver = registry.get(day).unwrap();
+                for user in &users {
+                    let input_file = input::user_input(user.as_deref(), &solver.name());
+                    if all_users && !input::path(&input_file).exists() {
+                        continue;
+                    }
+                    tasks.push((solver, input_file));
+                }
+            }
+            let mut outcomes = Vec::new();
+            pool::run_ordered(
+                config.jobs,
+                tasks,
+                |(solver, input_file)| solver.run_on(&input_file, part, &config),
+                |results| {
+                    reporter.report(&results);
+                    outcomes.extend(results);
+                },
+            );
+            reporter.finish();
+            add_to_history(run, &outcomes, &config);
+            Ok(())
+        }
+        Command::Bench {
+            days,
+            part,
+            samples,
+            timeout,
+        } => {
+            let days = days.select(&registry.days())?;
+            let mut config = BenchConfig {
+                timeout: Some(timeout.unwrap_or(DEFAULT_TIMEOUT)),
+                ..BenchConfig::default()
+            };
+            if let Some(samples) = samples {
+                config.samples = samples;
+            }
+            bench(registry, days, part, &config);
+            Ok(())
+        }
+        Command::Watch {
+            day,
+            part,
+            timeout,
+            rebuild,
+        } => {
+            let days = Days::Single(day).select(&registry.days())?;
+            watch(
+                registry.get(days[0]).unwrap(),
+                part,
+                &with_timeout(timeout),
+                rebuild,
+            )
+        }
+        Command::New { day } => {
+            for path in scaffold(day)? {
+                println!("Wrote {}", path.display());
+            }
+            println!(
+                "Add the answers of day{}_test to input/answers.txt, then rebuild",
+                day
+            );
+            Ok(())
+        }
+        Command::Fetch { day } => {
+            let path = input::path(&format!("day{}", day));
+            match client::fetch(Client::from_env, day, &path)? {
+                Fetched::Downloaded(bytes) => {
+                    println!("Downloaded {} bytes to {}", bytes, path.display())
+                }
+                Fetched::Cached => println!("{} is there already", path.display()),
+            }
+            Ok(())
+        }
+        Command::Submit { day, part } => {
+            let days = Days::Single(day).select(&registry.days())?;
+            let solver = registry.get(days[0]).unwrap();
+            let outcome = solver.run(Some(part), &with_timeout(None)).remove(0);
+            println!("{}", outcome);
+            let value = match (outcome.status, &outcome.value) {
+                (Status::Correct, _) => {
+                    return Err(format!("{} part {} is solved already", solver.name(), part))
+                }
+                (Status::Wrong, _) => {
+                    return Err("the answer is known and this is not it".to_string())
+                }
+                (_, Some(Answer::Error(_))) | (_, None) => {
+                    return Err("the solver gave no answer to submit".to_string())
+                }
+                (_, Some(value)) => value.clone(),
+            };
+            let verdict = submit::submit(
+                &Client::from_env()?,
+                &submit::submissions_file(),
+                day,
+                part,
+                &value.to_string(),
+            )?;
+            println!("{}", verdict);
+            if verdict == Verdict::Correct {
+                answers::record(&solver.name(), &part.to_string(), &value)?;
+                println!(
+                    "Recorded {} as the answer of {} part {}",
+                    value,
+                    solver.name(),
+                    part
+                );
+            }
+            Ok(())
+        }
+        Command::Readme => {
+            let config = with_timeout(None);
+            let mut outcomes = Vec::new();
+            for solver in registry.iter() {
+                outcomes.extend(solver.run(None, &config));
+            }
+            let path = readme::readme_file();
+            readme::update(&path, &to_markdown(&outcomes))?;
+            println!(
+                "Updated {} with {} days",
+                path.display(),
+                registry.days().len()
+            );
+            Ok(())
+        }
+        Command::Compare {
+            baseline,
+            threshold,
+        } => {
+            let entries = history::load(&history::history_file())?;
+            let changes = history::compare(
+                &entries,
+                baseline.as_deref(),
+                threshold.unwrap_or(DEFAULT_THRESHOLD),
+            )?;
+            for change in &changes {
+                println!("{}", history::describe(change));
+            }
+            let slower = changes.iter().filter(|c| c.regression).count();
+            if slower > 0 {
+                println!("{} of {} parts got slower", slower, changes.len());
+                std::process::exit(1);
+            }
+            Ok(())
+        }
+    }
+}
+
 fn main() {
-    solution_both_lines("day1", day1::solve1, day1::solve2, 55130, 54985);
-    solution_both_lines("day2", day2::solve1, day2::solve2, 2101, 58269);
-    solution_both_lines("day3", day3::solve1, day3::solve2, 553079, 84363105);
-    solution_both_lines("day4", day4::solve1, day4::solve2, 23028, 9236992);
-    solution_both_lines("day6", day6::solve1, day6::solve2, Ok(131376), Ok(34123437));
-    solution_both_lines("day7", day7::solve1, day7::solve2, 251121738, 251421071);
-    solution_both_lines("day8", day8::solve1, day8::solve2, 18023, 14449445933179);
-    solution_both_lines("day9", day9::solve1, day9::solve2, 1987402313, 900);
-    solution_both_lines("day10", day10::solve1, day10::solve2, 6909, 461);
-    solution_both_lines(
-        "day11",
-        day11::solve1,
-        day11::solve2,
-        10228230,
-        447073334102,
-    );
-    solution_both_lines("day15", day15::solve1, day15::solve2, 510801, 212763);
+    let registry = registry();
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let command = match parse_args(&args) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("{}\n{}", message, USAGE);
+            std::process::exit(2);
+        }
+    };
+    if let Err(message) = execute(&registry, command) {
+        eprintln!("{}", message);
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use advent_of_code_2023::{answers, input};
+
+    use super::registry;
+
+    #[test]
+    fn test_registered_days() {
+        let days = registry().days();
+        assert!(!days.is_empty());
+        assert!(days.windows(2).all(|pair| pair[0] < pair[1]), "{:?}", days);
+        assert!(days.iter().all(|day| (1..=25).contains(day)), "{:?}", days);
+    }
+
+    #[test]
+    fn test_registered_days_have_input() {
+        for solver in registry().iter() {
+            let path = input::path(&solver.name());
+            assert!(path.exists(), "{} is missing", path.display());
+        }
+    }
+
+    // parts that have never been solved
+    const UNSOLVED: [(&str, &str); 1] = [("day5", "2")];
+
+    #[test]
+    fn test_registered_days_have_answers() {
+        for solver in registry().iter() {
+            // a day scaffolded with new has an empty input until it is fetched
+            let input = std::fs::read_to_string(input::path(&solver.name())).unwrap_or_default();
+            if input.is_empty() {
+                continue;
+            }
+            for part in ["1", "2"] {
+                if UNSOLVED.contains(&(solver.name().as_str(), part)) {
+                    continue;
+                }
+                let expected = answers::expected(&solver.name(), part).unwrap();
+                assert!(
+                    expected.is_some(),
+                    "no answer for {} part {}",
+                    solver.name(),
+                    part
+                );
+            }
+        }
+    }
 }
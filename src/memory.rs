@@ -0,0 +1,153 @@
+// an allocator that counts the allocations of the current thread
+//
+// the binary installs it when built with the count-allocations feature:
+//
+//   #[global_allocator]
+//   static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+thread_local! {
+    static COUNTING: Cell<bool> = const { Cell::new(false) };
+    static COUNT: Cell<u64> = const { Cell::new(0) };
+    static BYTES: Cell<u64> = const { Cell::new(0) };
+    // can get negative when memory from another thread is freed
+    static LIVE: Cell<i64> = const { Cell::new(0) };
+    static PEAK: Cell<i64> = const { Cell::new(0) };
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Allocations {
+    pub count: u64,
+    pub bytes: u64,
+    // the most bytes allocated at the same time, on top of what was there before
+    pub peak: u64,
+}
+
+pub struct CountingAllocator;
+
+// the thread locals are gone while a thread shuts down, those allocations are not counted
+fn record(allocated: usize, freed: usize) {
+    let _ = COUNTING.try_with(|counting| {
+        if !counting.get() {
+            return;
+        }
+        if allocated > 0 {
+            COUNT.with(|c| c.set(c.get() + 1));
+            BYTES.with(|b| b.set(b.get() + allocated as u64));
+        }
+        LIVE.with(|live| {
+            live.set(live.get() + allocated as i64 - freed as i64);
+            PEAK.with(|peak| peak.set(peak.get().max(live.get())));
+        });
+    });
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record(layout.size(), 0);
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        record(layout.size(), 0);
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        record(0, layout.size());
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record(new_size, layout.size());
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+// runs f and counts its allocations on the current thread, None unless
+// built with the count-allocations feature, the binary installs the
+// CountingAllocator then
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Allocations>) {
+    COUNT.with(|c| c.set(0));
+    BYTES.with(|b| b.set(0));
+    LIVE.with(|l| l.set(0));
+    PEAK.with(|p| p.set(0));
+
+    COUNTING.with(|c| c.set(true));
+    let value = f();
+    COUNTING.with(|c| c.set(false));
+
+    let allocations = Allocations {
+        count: COUNT.with(|c| c.get()),
+        bytes: BYTES.with(|b| b.get()),
+        peak: PEAK.with(|p| p.get()) as u64,
+    };
+    (
+        value,
+        cfg!(feature = "count-allocations").then_some(allocations),
+    )
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    if bytes < 1024 {
+        format!("{}B", bytes)
+    } else if bytes < 1024 * 1024 {
+        format!("{:.1}KiB", bytes as f64 / 1024.0)
+    } else {
+        format!("{:.1}MiB", bytes as f64 / (1024.0 * 1024.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "count-allocations")]
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    #[cfg(feature = "count-allocations")]
+    #[test]
+    fn test_measure() {
+        let (_, allocations) = measure(|| {
+            let first = vec![0u8; 1000];
+            drop(first);
+            let second = vec![0u8; 600];
+            let third = vec![0u8; 400];
+            second.len() + third.len()
+        });
+        assert_eq!(
+            allocations,
+            Some(Allocations {
+                count: 3,
+                bytes: 2000,
+                peak: 1000
+            })
+        );
+    }
+
+    #[cfg(feature = "count-allocations")]
+    #[test]
+    fn test_measure_without_allocations() {
+        let (value, allocations) = measure(|| 6 * 7);
+        assert_eq!(value, 42);
+        assert_eq!(allocations.map(|a| a.count), Some(0));
+    }
+
+    #[cfg(not(feature = "count-allocations"))]
+    #[test]
+    fn test_measure_without_the_feature() {
+        let (value, allocations) = measure(|| vec![0u8; 1000].len());
+        assert_eq!(value, 1000);
+        assert_eq!(allocations, None);
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512B");
+        assert_eq!(format_bytes(1536), "1.5KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0MiB");
+    }
+}
@@ -0,0 +1,202 @@
+// the result of running one part of a day on one input file
+
+use std::fmt;
+use std::time::Duration;
+
+use crate::answer::Answer;
+use crate::input::split_user;
+use crate::memory::Allocations;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Correct,
+    Wrong,
+    // no answer is known for this input file and part
+    Unknown,
+    // no answer was known, the value has been stored as the answer
+    Recorded,
+    MissingInput,
+    ParseError,
+    Panicked,
+    // the deadline passed before parse or solve finished
+    Timeout,
+}
+
+impl Status {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Status::Correct => "correct",
+            Status::Wrong => "wrong",
+            Status::Unknown => "unknown",
+            Status::Recorded => "recorded",
+            Status::MissingInput => "missing_input",
+            Status::ParseError => "parse_error",
+            Status::Panicked => "panicked",
+            Status::Timeout => "timeout",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Timings {
+    pub read: Duration,
+    pub parse: Duration,
+    pub solve: Duration,
+}
+
+// only counted when the binary is built with the count-allocations feature
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Memory {
+    pub parse: Option<Allocations>,
+    pub solve: Option<Allocations>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Outcome {
+    // the input file name, e.g. day8_test2
+    pub input: String,
+    pub part: String,
+    // the answer of the solver, see src/answer.rs
+    pub value: Option<Answer>,
+    pub expected: Option<Answer>,
+    pub status: Status,
+    // details for the statuses that are not about the value
+    pub message: Option<String>,
+    pub timings: Timings,
+    pub memory: Memory,
+    // problems found in the input, see src/lint.rs
+    pub warnings: Vec<String>,
+    // the answer was taken from the result cache, there are no timings
+    pub cached: bool,
+}
+
+impl Outcome {
+    pub fn new(input: &str, part: &str, status: Status) -> Outcome {
+        Outcome {
+            input: input.to_string(),
+            part: part.to_string(),
+            value: None,
+            expected: None,
+            status,
+            message: None,
+            timings: Timings::default(),
+            memory: Memory::default(),
+            warnings: Vec::new(),
+            cached: false,
+        }
+    }
+
+    pub fn with_message(mut self, message: String) -> Outcome {
+        self.message = Some(message);
+        self
+    }
+
+    pub fn with_warnings(mut self, warnings: Vec<String>) -> Outcome {
+        self.warnings = warnings;
+        self
+    }
+
+    // the day number taken from the input file name, e.g. 8 for day8_test2 or alice/day8
+    pub fn day(&self) -> Option<u32> {
+        let digits: String = split_user(&self.input)
+            .1
+            .trim_start_matches("day")
+            .chars()
+            .take_while(|c| c.is_ascii_digit())
+            .collect();
+        digits.parse().ok()
+    }
+
+    // recorded answers count as correct, they are the reference from now on
+    pub fn is_correct(&self) -> bool {
+        matches!(self.status, Status::Correct | Status::Recorded)
+    }
+}
+
+impl Outcome {
+    // what happened, without the input file name
+    pub fn description(&self) -> String {
+        let value = shown(self.value.as_ref());
+        let expected = shown(self.expected.as_ref());
+        match self.status {
+            Status::Correct => format!("Correct Result {}", value),
+            Status::Wrong => format!("Expected {}, got {}", expected, value),
+            Status::Unknown => match &self.message {
+                Some(message) => format!(
+                    "No answer known for part {}, got {} ({})",
+                    self.part, value, message
+                ),
+                None => format!("No answer known for part {}, got {}", self.part, value),
+            },
+            Status::Recorded => format!("Recorded {} for part {}", value, self.part),
+            Status::MissingInput => format!(
+                "{} (part {})",
+                self.message.as_deref().unwrap_or("Missing input"),
+                self.part
+            ),
+            Status::ParseError => format!(
+                "Parse error: {} (part {})",
+                self.message.as_deref().unwrap_or("unknown"),
+                self.part
+            ),
+            Status::Panicked => format!(
+                "Panicked: {} (part {})",
+                self.message.as_deref().unwrap_or("unknown"),
+                self.part
+            ),
+            Status::Timeout => format!(
+                "Timeout: {} (part {})",
+                self.message.as_deref().unwrap_or("no result in time"),
+                self.part
+            ),
+        }
+    }
+}
+
+// how an answer is printed, also when there is none
+pub fn shown(answer: Option<&Answer>) -> String {
+    match answer {
+        Some(answer) => answer.to_string(),
+        None => "nothing".to_string(),
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.input, self.description())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let mut outcome = Outcome::new("day1", "1", Status::Wrong);
+        outcome.value = Some(Answer::from(142));
+        outcome.expected = Some(Answer::from(55130));
+        assert_eq!(outcome.to_string(), "day1: Expected 55130, got 142");
+
+        let outcome = Outcome::new("day3", "2", Status::MissingInput)
+            .with_message("Could not read input file input/day3.txt".to_string());
+        assert_eq!(
+            outcome.to_string(),
+            "day3: Could not read input file input/day3.txt (part 2)"
+        );
+    }
+
+    #[test]
+    fn test_day() {
+        assert_eq!(
+            Outcome::new("day8_test2", "2", Status::Correct).day(),
+            Some(8)
+        );
+        assert_eq!(Outcome::new("day15", "1", Status::Correct).day(), Some(15));
+        assert_eq!(
+            Outcome::new("alice/day7", "1", Status::Correct).day(),
+            Some(7)
+        );
+        assert_eq!(Outcome::new("stdin", "1", Status::Correct).day(), None);
+    }
+}
@@ -0,0 +1,81 @@
+// runs the days on several threads while keeping the report in day order
+
+use std::collections::BTreeMap;
+use std::sync::{mpsc, Mutex};
+use std::thread;
+
+// runs `work` on every task with up to `jobs` threads and passes the results
+// to `done` on the calling thread in the order of the tasks, each one as soon
+// as all results before it are there
+pub fn run_ordered<T, R>(
+    jobs: usize,
+    tasks: Vec<T>,
+    work: impl Fn(T) -> R + Sync,
+    mut done: impl FnMut(R),
+) where
+    T: Send,
+    R: Send,
+{
+    let threads = jobs.clamp(1, tasks.len().max(1));
+    let queue = Mutex::new(tasks.into_iter().enumerate());
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            let (queue, work, sender) = (&queue, &work, sender.clone());
+            scope.spawn(move || loop {
+                let next = queue.lock().unwrap_or_else(|e| e.into_inner()).next();
+                match next {
+                    Some((index, task)) => {
+                        if sender.send((index, work(task))).is_err() {
+                            break;
+                        }
+                    }
+                    None => break,
+                }
+            });
+        }
+        drop(sender);
+
+        let mut waiting = BTreeMap::new();
+        let mut next = 0;
+        for (index, result) in receiver {
+            waiting.insert(index, result);
+            while let Some(result) = waiting.remove(&next) {
+                done(result);
+                next += 1;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_results_in_task_order() {
+        // the first tasks take longest, so they finish last
+        let tasks: Vec<u64> = (0..8).collect();
+        let mut results = Vec::new();
+        run_ordered(
+            4,
+            tasks,
+            |n| {
+                thread::sleep(Duration::from_millis(40 - 5 * n));
+                n * n
+            },
+            |r| results.push(r),
+        );
+        assert_eq!(results, vec![0, 1, 4, 9, 16, 25, 36, 49]);
+    }
+
+    #[test]
+    fn test_single_job_and_no_tasks() {
+        let mut results = Vec::new();
+        run_ordered(1, vec!["a", "b"], |s| s.to_uppercase(), |r| results.push(r));
+        assert_eq!(results, vec!["A", "B"]);
+        run_ordered(4, Vec::<u8>::new(), |n| n, |_| panic!("there is no task"));
+    }
+}
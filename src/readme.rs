@@ -0,0 +1,89 @@
+// keeps a generated section of README.md up to date
+
+use std::path::{Path, PathBuf};
+
+use crate::input;
+
+pub const START_MARKER: &str = "<!-- results:start -->";
+pub const END_MARKER: &str = "<!-- results:end -->";
+
+// replaces everything between the markers with `section`, the markers are
+// added at the end of the document when they are not there yet
+pub fn replace_section(document: &str, section: &str) -> Result<String, String> {
+    let start = document.find(START_MARKER);
+    let end = document.find(END_MARKER);
+    match (start, end) {
+        (Some(start), Some(end)) if start < end => Ok(format!(
+            "{}{}\n{}\n{}",
+            &document[..start],
+            START_MARKER,
+            section.trim_end(),
+            &document[end..]
+        )),
+        (None, None) => {
+            let separator = if document.ends_with('\n') {
+                "\n"
+            } else {
+                "\n\n"
+            };
+            Ok(format!(
+                "{}{}## results\n\n{}\n{}\n{}\n",
+                document,
+                separator,
+                START_MARKER,
+                section.trim_end(),
+                END_MARKER
+            ))
+        }
+        _ => Err(format!(
+            "{} and {} do not enclose a section",
+            START_MARKER, END_MARKER
+        )),
+    }
+}
+
+// the README.md of the crate, next to the input directory
+pub fn readme_file() -> PathBuf {
+    input::input_dir().with_file_name("README.md")
+}
+
+pub fn update(path: &Path, section: &str) -> Result<(), String> {
+    let document = std::fs::read_to_string(path)
+        .map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+    let document = replace_section(&document, section)?;
+    std::fs::write(path, document).map_err(|e| format!("Could not write {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_readme_file() {
+        assert!(readme_file().ends_with("README.md"));
+        assert!(readme_file().with_file_name("input").is_dir());
+    }
+
+    #[test]
+    fn test_replace_section() {
+        let document =
+            "# title\n\n<!-- results:start -->\nold\n<!-- results:end -->\n\nmore text\n";
+        assert_eq!(
+            replace_section(document, "new\n").unwrap(),
+            "# title\n\n<!-- results:start -->\nnew\n<!-- results:end -->\n\nmore text\n"
+        );
+    }
+
+    #[test]
+    fn test_replace_section_appends() {
+        assert_eq!(
+            replace_section("# title\n", "new").unwrap(),
+            "# title\n\n## results\n\n<!-- results:start -->\nnew\n<!-- results:end -->\n"
+        );
+    }
+
+    #[test]
+    fn test_replace_section_broken_markers() {
+        assert!(replace_section("<!-- results:end --> <!-- results:start -->", "new").is_err());
+    }
+}
@@ -0,0 +1,425 @@
+// reporters turn the outcomes of a run into output
+
+use crate::answer::Answer;
+use crate::bench::format_duration;
+use crate::memory::{format_bytes, Allocations};
+use crate::outcome::{Outcome, Status};
+
+pub trait Reporter {
+    // called with the outcomes of one input file, in part order
+    fn report(&mut self, outcomes: &[Outcome]);
+
+    // called once after all days ran
+    fn finish(&mut self) {}
+}
+
+fn describe_allocations(allocations: &Allocations) -> String {
+    format!(
+        "{} allocations ({}, peak {})",
+        allocations.count,
+        format_bytes(allocations.bytes),
+        format_bytes(allocations.peak)
+    )
+}
+
+// prints the warnings about the input, one line per outcome, the timings of every input file and the
+// allocations when they are counted
+#[derive(Default)]
+pub struct TextReporter;
+
+impl Reporter for TextReporter {
+    fn report(&mut self, outcomes: &[Outcome]) {
+        // all outcomes of an input file share its warnings
+        if let Some(first) = outcomes.first() {
+            for warning in &first.warnings {
+                println!("warning: {}", warning);
+            }
+        }
+        for outcome in outcomes {
+            println!("{}", outcome);
+        }
+
+        let solved: Vec<&Outcome> = outcomes
+            .iter()
+            .filter(|o| !matches!(o.status, Status::MissingInput | Status::ParseError))
+            .collect();
+        if let Some(first) = solved.first() {
+            if first.cached {
+                println!("{}: cached", first.input);
+                return;
+            }
+            let mut line = format!(
+                "{}: read {}µs, parse {}µs",
+                first.input,
+                first.timings.read.as_micros(),
+                first.timings.parse.as_micros()
+            );
+            for outcome in &solved {
+                line.push_str(&format!(
+                    ", part {} {}µs",
+                    outcome.part,
+                    outcome.timings.solve.as_micros()
+                ));
+            }
+            println!("{}", line);
+
+            if let Some(parse) = &first.memory.parse {
+                let mut line = format!("{}: parse {}", first.input, describe_allocations(parse));
+                for outcome in &solved {
+                    if let Some(solve) = &outcome.memory.solve {
+                        line.push_str(&format!(
+                            ", part {} {}",
+                            outcome.part,
+                            describe_allocations(solve)
+                        ));
+                    }
+                }
+                println!("{}", line);
+            }
+        }
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut json = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+fn json_option(value: Option<&str>) -> String {
+    value.map(json_string).unwrap_or_else(|| "null".to_string())
+}
+
+// answers are strings, so long numbers and text answers come out the same
+fn json_answer(answer: Option<&Answer>) -> String {
+    json_option(answer.map(|a| a.to_string()).as_deref())
+}
+
+fn json_array(values: &[String]) -> String {
+    let values: Vec<String> = values.iter().map(|v| json_string(v)).collect();
+    format!("[{}]", values.join(", "))
+}
+
+fn json_allocations(allocations: Option<&Allocations>) -> String {
+    match allocations {
+        Some(a) => format!(
+            "{{\"count\": {}, \"bytes\": {}, \"peak\": {}}}",
+            a.count, a.bytes, a.peak
+        ),
+        None => "null".to_string(),
+    }
+}
+
+// one json object per outcome, timings are in microseconds, allocations are
+// null unless they are counted
+pub fn to_json(outcomes: &[Outcome]) -> String {
+    let results: Vec<String> = outcomes
+        .iter()
+        .map(|o| {
+            format!(
+                concat!(
+                    "    {{\"day\": {}, \"input\": {}, \"part\": {}, ",
+                    "\"answer\": {}, \"expected\": {}, \"status\": {}, \"message\": {}, \"cached\": {}, ",
+                    "\"timings_us\": {{\"read\": {}, \"parse\": {}, \"solve\": {}}}, ",
+                    "\"allocations\": {{\"parse\": {}, \"solve\": {}}}, \"warnings\": {}}}"
+                ),
+                o.day().map(|d| d.to_string()).unwrap_or_else(|| "null".to_string()),
+                json_string(&o.input),
+                json_string(&o.part),
+                json_answer(o.value.as_ref()),
+                json_answer(o.expected.as_ref()),
+                json_string(o.status.as_str()),
+                json_option(o.message.as_deref()),
+                o.cached,
+                o.timings.read.as_micros(),
+                o.timings.parse.as_micros(),
+                o.timings.solve.as_micros(),
+                json_allocations(o.memory.parse.as_ref()),
+                json_allocations(o.memory.solve.as_ref()),
+                json_array(&o.warnings)
+            )
+        })
+        .collect();
+    format!("{{\n  \"results\": [\n{}\n  ]\n}}", results.join(",\n"))
+}
+
+// collects all outcomes and prints them as one json document at the end
+#[derive(Default)]
+pub struct JsonReporter {
+    outcomes: Vec<Outcome>,
+}
+
+impl Reporter for JsonReporter {
+    fn report(&mut self, outcomes: &[Outcome]) {
+        self.outcomes.extend_from_slice(outcomes);
+    }
+
+    fn finish(&mut self) {
+        println!("{}", to_json(&self.outcomes));
+    }
+}
+
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn seconds(outcome: &Outcome) -> f64 {
+    let timings = &outcome.timings;
+    (timings.read + timings.parse + timings.solve).as_secs_f64()
+}
+
+// one testsuite per day with one testcase per input file and part
+pub fn to_junit(outcomes: &[Outcome]) -> String {
+    let mut days: Vec<(String, Vec<&Outcome>)> = Vec::new();
+    for outcome in outcomes {
+        let day = match outcome.day() {
+            Some(day) => format!("day{}", day),
+            None => outcome.input.clone(),
+        };
+        match days.iter_mut().find(|(name, _)| *name == day) {
+            Some((_, cases)) => cases.push(outcome),
+            None => days.push((day, vec![outcome])),
+        }
+    }
+
+    let count = |cases: &[&Outcome], statuses: &[Status]| {
+        cases
+            .iter()
+            .filter(|o| statuses.contains(&o.status))
+            .count()
+    };
+    let failures = [Status::Wrong];
+    let errors = [
+        Status::MissingInput,
+        Status::ParseError,
+        Status::Panicked,
+        Status::Timeout,
+    ];
+    let skipped = [Status::Unknown];
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let all: Vec<&Outcome> = outcomes.iter().collect();
+    xml.push_str(&format!(
+        "<testsuites name=\"advent_of_code_2023\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{:.6}\">\n",
+        all.len(),
+        count(&all, &failures),
+        count(&all, &errors),
+        count(&all, &skipped),
+        outcomes.iter().map(seconds).sum::<f64>()
+    ));
+    for (day, cases) in &days {
+        xml.push_str(&format!(
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{:.6}\">\n",
+            xml_escape(day),
+            cases.len(),
+            count(cases, &failures),
+            count(cases, &errors),
+            count(cases, &skipped),
+            cases.iter().map(|o| seconds(o)).sum::<f64>()
+        ));
+        for outcome in cases {
+            let open = format!(
+                "    <testcase classname=\"{}\" name=\"{} part {}\" time=\"{:.6}\"",
+                xml_escape(day),
+                xml_escape(&outcome.input),
+                xml_escape(&outcome.part),
+                seconds(outcome)
+            );
+            let element = match outcome.status {
+                Status::Correct | Status::Recorded => None,
+                Status::Wrong => Some("failure"),
+                Status::Unknown => Some("skipped"),
+                Status::MissingInput | Status::ParseError | Status::Panicked | Status::Timeout => {
+                    Some("error")
+                }
+            };
+            match element {
+                None => xml.push_str(&format!("{}/>\n", open)),
+                Some(element) => xml.push_str(&format!(
+                    "{}>\n      <{} type=\"{}\" message=\"{}\"/>\n    </testcase>\n",
+                    open,
+                    element,
+                    outcome.status.as_str(),
+                    xml_escape(&outcome.description())
+                )),
+            }
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+    xml.push_str("</testsuites>");
+    xml
+}
+
+// collects all outcomes and prints them as junit xml at the end
+#[derive(Default)]
+pub struct JunitReporter {
+    outcomes: Vec<Outcome>,
+}
+
+impl Reporter for JunitReporter {
+    fn report(&mut self, outcomes: &[Outcome]) {
+        self.outcomes.extend_from_slice(outcomes);
+    }
+
+    fn finish(&mut self) {
+        println!("{}", to_junit(&self.outcomes));
+    }
+}
+
+// a markdown table with one row per day, one star per correct part
+pub fn to_markdown(outcomes: &[Outcome]) -> String {
+    let mut table = String::from("| Day | Part 1 | Part 2 | Time part 1 | Time part 2 | Stars |\n");
+    table.push_str("|----:|-------:|-------:|------------:|------------:|:------|\n");
+
+    let mut days: Vec<u32> = outcomes.iter().filter_map(|o| o.day()).collect();
+    days.dedup();
+    for day in days {
+        let part = |part: &str| {
+            outcomes
+                .iter()
+                .find(|o| o.day() == Some(day) && o.part == part)
+        };
+        let answer = |part: Option<&Outcome>| match part.and_then(|o| o.value.as_ref()) {
+            Some(value) => value.to_string(),
+            None => "-".to_string(),
+        };
+        let time = |part: Option<&Outcome>| match part {
+            Some(outcome) if outcome.value.is_some() => format_duration(outcome.timings.solve),
+            _ => "-".to_string(),
+        };
+        let (part1, part2) = (part("1"), part("2"));
+        let stars = [part1, part2]
+            .iter()
+            .filter(|o| o.map(|o| o.is_correct()).unwrap_or(false))
+            .count();
+
+        table.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} |\n",
+            day,
+            answer(part1),
+            answer(part2),
+            time(part1),
+            time(part2),
+            "⭐".repeat(stars)
+        ));
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn outcome() -> Outcome {
+        let mut outcome = Outcome::new("day8_test2", "2", Status::Wrong);
+        outcome.value = Some(Answer::from(7));
+        outcome.expected = Some(Answer::from(6));
+        outcome.timings.solve = Duration::from_micros(42);
+        outcome.memory.solve = Some(Allocations {
+            count: 3,
+            bytes: 2048,
+            peak: 1024,
+        });
+        outcome.warnings = vec!["input/day8_test2.txt line 3: empty line at the end".to_string()];
+        outcome
+    }
+
+    #[test]
+    fn test_json_string() {
+        assert_eq!(json_string("a\"b\\c\nd\u{1}"), "\"a\\\"b\\\\c\\nd\\u0001\"");
+    }
+
+    #[test]
+    fn test_describe_allocations() {
+        let allocations = Allocations {
+            count: 12,
+            bytes: 1536,
+            peak: 512,
+        };
+        assert_eq!(
+            describe_allocations(&allocations),
+            "12 allocations (1.5KiB, peak 512B)"
+        );
+    }
+
+    #[test]
+    fn test_to_json() {
+        let missing =
+            Outcome::new("day3", "1", Status::MissingInput).with_message("no file".to_string());
+        assert_eq!(
+            to_json(&[outcome(), missing]),
+            concat!(
+                "{\n  \"results\": [\n",
+                "    {\"day\": 8, \"input\": \"day8_test2\", \"part\": \"2\", \"answer\": \"7\", \"expected\": \"6\", ",
+                "\"status\": \"wrong\", \"message\": null, \"cached\": false, \"timings_us\": {\"read\": 0, \"parse\": 0, \"solve\": 42}, ",
+                "\"allocations\": {\"parse\": null, \"solve\": {\"count\": 3, \"bytes\": 2048, \"peak\": 1024}}, ",
+                "\"warnings\": [\"input/day8_test2.txt line 3: empty line at the end\"]},\n",
+                "    {\"day\": 3, \"input\": \"day3\", \"part\": \"1\", \"answer\": null, \"expected\": null, ",
+                "\"status\": \"missing_input\", \"message\": \"no file\", \"cached\": false, \"timings_us\": {\"read\": 0, \"parse\": 0, \"solve\": 0}, ",
+                "\"allocations\": {\"parse\": null, \"solve\": null}, \"warnings\": []}\n",
+                "  ]\n}"
+            )
+        );
+    }
+
+    #[test]
+    fn test_to_junit() {
+        let mut correct = Outcome::new("day8", "1", Status::Correct);
+        correct.value = Some(Answer::from(18023));
+        let missing =
+            Outcome::new("day3", "1", Status::MissingInput).with_message("no <file>".to_string());
+        assert_eq!(
+            to_junit(&[correct, outcome(), missing]),
+            concat!(
+                "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+                "<testsuites name=\"advent_of_code_2023\" tests=\"3\" failures=\"1\" errors=\"1\" skipped=\"0\" time=\"0.000042\">\n",
+                "  <testsuite name=\"day8\" tests=\"2\" failures=\"1\" errors=\"0\" skipped=\"0\" time=\"0.000042\">\n",
+                "    <testcase classname=\"day8\" name=\"day8 part 1\" time=\"0.000000\"/>\n",
+                "    <testcase classname=\"day8\" name=\"day8_test2 part 2\" time=\"0.000042\">\n",
+                "      <failure type=\"wrong\" message=\"Expected 6, got 7\"/>\n",
+                "    </testcase>\n",
+                "  </testsuite>\n",
+                "  <testsuite name=\"day3\" tests=\"1\" failures=\"0\" errors=\"1\" skipped=\"0\" time=\"0.000000\">\n",
+                "    <testcase classname=\"day3\" name=\"day3 part 1\" time=\"0.000000\">\n",
+                "      <error type=\"missing_input\" message=\"no &lt;file&gt; (part 1)\"/>\n",
+                "    </testcase>\n",
+                "  </testsuite>\n",
+                "</testsuites>"
+            )
+        );
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        let mut correct = Outcome::new("day8", "1", Status::Correct);
+        correct.value = Some(Answer::from(18023));
+        correct.timings.solve = Duration::from_micros(1_500);
+        let missing = Outcome::new("day8", "2", Status::MissingInput);
+        assert_eq!(
+            to_markdown(&[correct, missing]),
+            concat!(
+                "| Day | Part 1 | Part 2 | Time part 1 | Time part 2 | Stars |\n",
+                "|----:|-------:|-------:|------------:|------------:|:------|\n",
+                "| 8 | 18023 | - | 1.50ms | - | ⭐ |\n"
+            )
+        );
+    }
+}
@@ -0,0 +1,275 @@
+// creates the files of a new day and registers it in src/main.rs
+
+use std::path::{Path, PathBuf};
+
+use crate::input;
+
+// DAY_NUMBER is replaced with the number of the day
+const TEMPLATE: &str = r#"// day DAY_NUMBER
+
+use advent_of_code_2023::day::Day;
+
+pub fn solve1(_input: &str) -> usize {
+    todo!("part 1 of day DAY_NUMBER")
+}
+
+pub fn solve2(_input: &str) -> usize {
+    todo!("part 2 of day DAY_NUMBER")
+}
+
+pub struct DayDAY_NUMBER;
+
+impl Day for DayDAY_NUMBER {
+    const DAY: u32 = DAY_NUMBER;
+    const VERSION: &'static str = "1";
+    // parse the input into the data the parts need once the puzzle is known
+    type Input = String;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> String {
+        input.to_string()
+    }
+
+    fn part1(input: &String) -> usize {
+        solve1(input)
+    }
+
+    fn part2(input: &String) -> usize {
+        solve2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use advent_of_code_2023::day::Day;
+    use advent_of_code_2023::solution_parse_str;
+
+    use super::*;
+
+    // the parts are not solved yet and the input files are empty, remove
+    // the ignore of a test once its part is solved and its answer known
+    #[test]
+    #[ignore = "day DAY_NUMBER is not solved yet"]
+    fn test_solve1_test() {
+        let result = solution_parse_str("dayDAY_NUMBER_test", "1", DayDAY_NUMBER::parse, DayDAY_NUMBER::part1);
+        assert!(result.is_correct(), "{}", result);
+    }
+
+    #[test]
+    #[ignore = "day DAY_NUMBER is not solved yet"]
+    fn test_solve1() {
+        let result = solution_parse_str("dayDAY_NUMBER", "1", DayDAY_NUMBER::parse, DayDAY_NUMBER::part1);
+        assert!(result.is_correct(), "{}", result);
+    }
+
+    #[test]
+    #[ignore = "day DAY_NUMBER is not solved yet"]
+    fn test_solve2_test() {
+        let result = solution_parse_str("dayDAY_NUMBER_test", "2", DayDAY_NUMBER::parse, DayDAY_NUMBER::part2);
+        assert!(result.is_correct(), "{}", result);
+    }
+
+    #[test]
+    #[ignore = "day DAY_NUMBER is not solved yet"]
+    fn test_solve2() {
+        let result = solution_parse_str("dayDAY_NUMBER", "2", DayDAY_NUMBER::parse, DayDAY_NUMBER::part2);
+        assert!(result.is_correct(), "{}", result);
+    }
+}
+"#;
+
+pub fn day_module(day: u32) -> String {
+    TEMPLATE.replace("DAY_NUMBER", &day.to_string())
+}
+
+fn day_number(line: &str, prefix: &str) -> Option<u32> {
+    line.trim()
+        .strip_prefix(prefix)?
+        .split(|c: char| !c.is_ascii_digit())
+        .next()?
+        .parse()
+        .ok()
+}
+
+// adds `mod dayN;` to the other mod lines, which are sorted by name, and
+// registers the day after the days with a lower number
+pub fn register_day(main_rs: &str, day: u32) -> Result<String, String> {
+    let module = format!("mod day{};", day);
+    if main_rs.lines().any(|line| line.trim() == module) {
+        return Err(format!("day{} is in src/main.rs already", day));
+    }
+    let mut lines: Vec<String> = main_rs.lines().map(|line| line.to_string()).collect();
+
+    let mods: Vec<usize> = (0..lines.len())
+        .filter(|&i| day_number(&lines[i], "mod day").is_some())
+        .collect();
+    let name = |line: &str| {
+        line.trim()
+            .trim_start_matches("mod ")
+            .trim_end_matches(';')
+            .to_string()
+    };
+    let at = match mods.iter().find(|&&i| name(&lines[i]) > name(&module)) {
+        Some(&i) => i,
+        None => {
+            mods.last()
+                .ok_or("there are no mod lines of days in src/main.rs")?
+                + 1
+        }
+    };
+    lines.insert(at, module);
+
+    let registers: Vec<usize> = (0..lines.len())
+        .filter(|&i| day_number(&lines[i], "registry.register(day").is_some())
+        .collect();
+    let at = match registers
+        .iter()
+        .find(|&&i| day_number(&lines[i], "registry.register(day") > Some(day))
+    {
+        Some(&i) => i,
+        None => {
+            registers
+                .last()
+                .ok_or("there are no days registered in src/main.rs")?
+                + 1
+        }
+    };
+    lines.insert(
+        at,
+        format!("    registry.register(day{}::Day{});", day, day),
+    );
+
+    Ok(lines.join("\n") + "\n")
+}
+
+// writes src/dayN.rs and registers it, creates the input files that are
+// missing; nothing is written when the source file or the module exist
+pub fn scaffold(day: u32) -> Result<Vec<PathBuf>, String> {
+    scaffold_in(&input::input_dir(), day)
+}
+
+// the input file stays empty until fetch downloads it, see src/client.rs
+fn scaffold_in(input_dir: &Path, day: u32) -> Result<Vec<PathBuf>, String> {
+    let src = input_dir.with_file_name("src");
+    let source = src.join(format!("day{}.rs", day));
+    if source.exists() {
+        return Err(format!("{} exists already", source.display()));
+    }
+    let main = src.join("main.rs");
+    let main_rs = std::fs::read_to_string(&main)
+        .map_err(|e| format!("Could not read {}: {}", main.display(), e))?;
+    let main_rs = register_day(&main_rs, day)?;
+
+    let write = |path: &PathBuf, content: &str| {
+        std::fs::write(path, content)
+            .map_err(|e| format!("Could not write {}: {}", path.display(), e))
+    };
+    write(&source, &day_module(day))?;
+    write(&main, &main_rs)?;
+    let mut created = vec![source, main];
+    for input_file in [format!("day{}", day), format!("day{}_test", day)] {
+        let path = input_dir.join(format!("{}.txt", input_file));
+        if !path.exists() {
+            write(&path, "")?;
+            created.push(path);
+        }
+    }
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::client::{fetch, Client, Fetched};
+    use crate::testing::{serve, temp_path};
+
+    const MAIN_RS: &str = "use x;
+
+mod day1;
+mod day10;
+mod day15;
+mod day2;
+mod day9;
+
+fn registry() -> Registry {
+    let mut registry = Registry::new();
+    registry.register(day1::Day1);
+    registry.register(day2::Day2);
+    registry.register(day9::Day9);
+    registry.register(day10::Day10);
+    registry.register(day15::Day15);
+    registry
+}
+";
+
+    #[test]
+    fn test_register_day() {
+        let main_rs = register_day(MAIN_RS, 12).unwrap();
+        assert!(
+            main_rs.contains("mod day10;\nmod day12;\nmod day15;\n"),
+            "{}",
+            main_rs
+        );
+        assert!(main_rs.contains(
+            "    registry.register(day10::Day10);\n    registry.register(day12::Day12);\n    registry.register(day15::Day15);\n"
+        ));
+
+        let main_rs = register_day(MAIN_RS, 25).unwrap();
+        assert!(
+            main_rs.contains("mod day2;\nmod day25;\nmod day9;\n"),
+            "{}",
+            main_rs
+        );
+        assert!(main_rs.contains("    registry.register(day15::Day15);\n    registry.register(day25::Day25);\n    registry\n"));
+
+        assert_eq!(
+            register_day(MAIN_RS, 9).unwrap_err(),
+            "day9 is in src/main.rs already"
+        );
+    }
+
+    #[test]
+    fn test_day_module() {
+        let module = day_module(12);
+        assert!(module.starts_with("// day 12\n"));
+        assert!(module.contains("impl Day for Day12 {\n    const DAY: u32 = 12;\n"));
+        assert!(module
+            .contains("solution_parse_str(\"day12_test\", \"2\", Day12::parse, Day12::part2)"));
+        assert_eq!(
+            module
+                .matches("#[ignore = \"day 12 is not solved yet\"]")
+                .count(),
+            4
+        );
+        assert!(!module.contains("DAY_NUMBER"));
+    }
+
+    #[test]
+    fn test_fetch_after_scaffold() {
+        let root = temp_path("crate");
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::create_dir_all(root.join("input")).unwrap();
+        std::fs::write(root.join("src/main.rs"), MAIN_RS).unwrap();
+        let input_dir = root.join("input");
+        scaffold_in(&input_dir, 12).unwrap();
+
+        let (url, _requests) = serve(vec![(200, "???.### 1,1,3\n")]);
+        let client = Client::new(&url, "53616c7465645f5f", Duration::ZERO, &temp_path("last"));
+        let path = input_dir.join("day12.txt");
+        let fetched = fetch(|| Ok(client), 12, &path);
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_dir_all(&root).unwrap();
+        assert_eq!(fetched, Ok(Fetched::Downloaded(14)));
+        assert_eq!(content, "???.### 1,1,3\n");
+    }
+
+    #[test]
+    fn test_registered_days_are_not_scaffolded() {
+        assert!(scaffold(1)
+            .unwrap_err()
+            .ends_with("src/day1.rs exists already"));
+    }
+}
@@ -0,0 +1,331 @@
+// submits answers to the website and remembers the ones it rejected
+//
+// every answer the website gave a verdict on is kept in
+// input/submissions.txt, one line per answer:
+//
+//   day7  1  too_high  251121739
+//
+// answers in there, and numbers beyond a too_high or too_low one, are known
+// to be wrong and never submitted again
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::client::Client;
+use crate::input;
+use crate::read_optional;
+
+lazy_static! {
+    static ref REGEX_WAIT: Regex = Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+}
+
+const HEADER: &str = "# answers given to the website and its verdict, see src/submit.rs\n";
+
+pub fn submissions_file() -> PathBuf {
+    input::input_dir().join("submissions.txt")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    // an answer was given too recently, try again after that many seconds
+    Wait(u64),
+    // the part is solved already, the website does not check answers for it any more
+    AlreadySolved,
+}
+
+impl Verdict {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+            Verdict::Wrong => "wrong",
+            Verdict::Wait(_) => "wait",
+            Verdict::AlreadySolved => "already_solved",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Verdict> {
+        match value {
+            "correct" => Some(Verdict::Correct),
+            "too_high" => Some(Verdict::TooHigh),
+            "too_low" => Some(Verdict::TooLow),
+            "wrong" => Some(Verdict::Wrong),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "That's the right answer"),
+            Verdict::TooHigh => write!(f, "Wrong, the answer is too high"),
+            Verdict::TooLow => write!(f, "Wrong, the answer is too low"),
+            Verdict::Wrong => write!(f, "Wrong answer"),
+            Verdict::Wait(seconds) => {
+                write!(f, "An answer was given too recently, wait {}s", seconds)
+            }
+            Verdict::AlreadySolved => write!(f, "This part is solved already"),
+        }
+    }
+}
+
+// the verdict in the html page the website answers with
+pub fn parse_response(body: &str) -> Result<Verdict, String> {
+    if body.contains("That's the right answer") {
+        Ok(Verdict::Correct)
+    } else if body.contains("That's not the right answer") {
+        if body.contains("your answer is too high") {
+            Ok(Verdict::TooHigh)
+        } else if body.contains("your answer is too low") {
+            Ok(Verdict::TooLow)
+        } else {
+            Ok(Verdict::Wrong)
+        }
+    } else if body.contains("You gave an answer too recently") {
+        let seconds = REGEX_WAIT
+            .captures(body)
+            .map(|c| {
+                let minutes: u64 = c.get(1).map(|m| m.as_str().parse().unwrap()).unwrap_or(0);
+                minutes * 60 + c[2].parse::<u64>().unwrap()
+            })
+            .unwrap_or(60);
+        Ok(Verdict::Wait(seconds))
+    } else if body.contains("You don't seem to be solving the right level") {
+        Ok(Verdict::AlreadySolved)
+    } else {
+        Err("the website answered with a page that has no verdict".to_string())
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Submissions {
+    // input file, part, verdict and answer in file order
+    entries: Vec<(String, String, Verdict, String)>,
+}
+
+impl Submissions {
+    // broken lines are dropped
+    pub fn load(path: &Path) -> Result<Submissions, String> {
+        let entries = read_optional(path)?
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .filter_map(
+                |line| match line.split('\t').collect::<Vec<&str>>().as_slice() {
+                    [input, part, verdict, answer] => Some((
+                        input.to_string(),
+                        part.to_string(),
+                        Verdict::parse(verdict)?,
+                        answer.to_string(),
+                    )),
+                    _ => None,
+                },
+            )
+            .collect();
+        Ok(Submissions { entries })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let mut content = String::from(HEADER);
+        for (input, part, verdict, answer) in &self.entries {
+            content.push_str(&format!(
+                "{}\t{}\t{}\t{}\n",
+                input,
+                part,
+                verdict.as_str(),
+                answer
+            ));
+        }
+        std::fs::write(path, content)
+            .map_err(|e| format!("Could not write {}: {}", path.display(), e))
+    }
+
+    // only verdicts about the answer itself are remembered
+    pub fn add(&mut self, input: &str, part: &str, verdict: Verdict, answer: &str) {
+        if matches!(
+            verdict,
+            Verdict::Correct | Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong
+        ) {
+            self.entries.push((
+                input.to_string(),
+                part.to_string(),
+                verdict,
+                answer.to_string(),
+            ));
+        }
+    }
+
+    // why the answer is known to be wrong, None if it may be right
+    pub fn known_wrong(&self, input: &str, part: &str, answer: &str) -> Option<String> {
+        let number = answer.parse::<i128>().ok();
+        self.entries
+            .iter()
+            .filter(|(i, p, _, _)| i == input && p == part)
+            .find_map(|(_, _, verdict, given)| {
+                let bound = given.parse::<i128>().ok();
+                match (verdict, number, bound) {
+                    (Verdict::Correct, _, _) => None,
+                    _ if given == answer => Some(format!("{} was rejected before", answer)),
+                    (Verdict::TooHigh, Some(n), Some(b)) if n >= b => Some(format!(
+                        "{} is too high, {} was too high already",
+                        answer, given
+                    )),
+                    (Verdict::TooLow, Some(n), Some(b)) if n <= b => Some(format!(
+                        "{} is too low, {} was too low already",
+                        answer, given
+                    )),
+                    _ => None,
+                }
+            })
+    }
+}
+
+// submits the answer of a part unless it is known to be wrong and remembers the verdict
+pub fn submit(
+    client: &Client,
+    path: &Path,
+    day: u32,
+    part: u8,
+    answer: &str,
+) -> Result<Verdict, String> {
+    let input = format!("day{}", day);
+    let part = part.to_string();
+    let mut submissions = Submissions::load(path)?;
+    if let Some(reason) = submissions.known_wrong(&input, &part, answer) {
+        return Err(format!("not submitting, {}", reason));
+    }
+
+    let body = client
+        .post_form(
+            &format!("{}/answer", day),
+            &[("level", &part), ("answer", answer)],
+        )
+        .map_err(|(code, body)| match code {
+            0 => format!("Could not submit the answer: {}", body),
+            _ => format!("Could not submit the answer: status {}", code),
+        })?;
+    let verdict = parse_response(&body)?;
+    submissions.add(&input, &part, verdict, answer);
+    submissions.save(path)?;
+    Ok(verdict)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::testing::{serve, temp_path};
+
+    use super::*;
+
+    const TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high.  If you're stuck, \
+        make sure you're using the full input data; ... Please wait one minute before trying again.</p></article>";
+
+    #[test]
+    fn test_parse_response() {
+        assert_eq!(
+            parse_response("<p>That's the right answer!  You are one gold star closer"),
+            Ok(Verdict::Correct)
+        );
+        assert_eq!(parse_response(TOO_HIGH), Ok(Verdict::TooHigh));
+        assert_eq!(
+            parse_response("That's not the right answer; your answer is too low."),
+            Ok(Verdict::TooLow)
+        );
+        assert_eq!(
+            parse_response("That's not the right answer.  If you're stuck"),
+            Ok(Verdict::Wrong)
+        );
+        assert_eq!(
+            parse_response("You gave an answer too recently; you have to wait after submitting. You have 4m 12s left to wait."),
+            Ok(Verdict::Wait(252))
+        );
+        assert_eq!(
+            parse_response("You gave an answer too recently ... You have 43s left to wait."),
+            Ok(Verdict::Wait(43))
+        );
+        assert_eq!(
+            parse_response(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            ),
+            Ok(Verdict::AlreadySolved)
+        );
+        assert!(parse_response("<html>maintenance</html>").is_err());
+    }
+
+    #[test]
+    fn test_known_wrong() {
+        let mut submissions = Submissions::default();
+        submissions.add("day7", "1", Verdict::TooHigh, "300");
+        submissions.add("day7", "1", Verdict::TooLow, "100");
+        submissions.add("day7", "1", Verdict::Wrong, "ABC");
+        submissions.add("day7", "1", Verdict::Wait(30), "200");
+        assert_eq!(
+            submissions.known_wrong("day7", "1", "300"),
+            Some("300 was rejected before".to_string())
+        );
+        assert_eq!(
+            submissions.known_wrong("day7", "1", "301"),
+            Some("301 is too high, 300 was too high already".to_string())
+        );
+        assert_eq!(
+            submissions.known_wrong("day7", "1", "99"),
+            Some("99 is too low, 100 was too low already".to_string())
+        );
+        assert_eq!(
+            submissions.known_wrong("day7", "1", "ABC"),
+            Some("ABC was rejected before".to_string())
+        );
+        assert_eq!(submissions.known_wrong("day7", "1", "200"), None);
+        assert_eq!(submissions.known_wrong("day7", "2", "300"), None);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let path = temp_path("submissions.txt");
+        let mut submissions = Submissions::default();
+        submissions.add("day7", "1", Verdict::TooLow, "100");
+        submissions.add("day7", "1", Verdict::Correct, "251121738");
+        submissions.save(&path).unwrap();
+        assert_eq!(Submissions::load(&path).unwrap(), submissions);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_submit() {
+        let (url, requests) = serve(vec![(200, TOO_HIGH)]);
+        let client = Client::new(
+            &url,
+            "53616c7465645f5f",
+            Duration::ZERO,
+            &temp_path("last_request"),
+        );
+        let path = temp_path("submit.txt");
+
+        assert_eq!(
+            submit(&client, &path, 7, 2, "251421072"),
+            Ok(Verdict::TooHigh)
+        );
+        let request = requests.recv().unwrap();
+        assert!(
+            request.starts_with("POST /2023/day/7/answer HTTP/1.1\r\n"),
+            "{}",
+            request
+        );
+        assert!(request.ends_with("level=2&answer=251421072"), "{}", request);
+
+        assert_eq!(
+            submit(&client, &path, 7, 2, "251421080").unwrap_err(),
+            "not submitting, 251421080 is too high, 251421072 was too high already"
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+}
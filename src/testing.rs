@@ -0,0 +1,57 @@
+// helpers for the tests of the modules that use files or the network
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+static TEMP_FILES: AtomicUsize = AtomicUsize::new(0);
+
+// a new path in the temp directory for every call, so tests running at the
+// same time never share a file, the name is kept at the end
+pub fn temp_path(name: &str) -> PathBuf {
+    let n = TEMP_FILES.fetch_add(1, Ordering::SeqCst);
+    std::env::temp_dir().join(format!("aoc_{}_{}_{}", std::process::id(), n, name))
+}
+
+// a server that answers the given responses in order, one per
+// connection, and sends every request it got, head and body, back; it
+// stops after the last response, so a request more than expected fails
+pub fn serve(responses: Vec<(u16, &'static str)>) -> (String, mpsc::Receiver<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for (code, body) in responses {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    length = value.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" || line.is_empty() {
+                    break;
+                }
+            }
+            let mut content = vec![0; length];
+            reader.read_exact(&mut content).unwrap();
+            request.push_str(&String::from_utf8_lossy(&content));
+            let _ = sender.send(request);
+            let response = format!(
+                "HTTP/1.1 {} Stub\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                code,
+                body.len(),
+                body
+            );
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+        }
+    });
+    (url, receiver)
+}
@@ -0,0 +1,197 @@
+// reruns a day whenever one of its input files changes, and with rebuild
+// also when its source file changes
+
+use std::path::{Path, PathBuf};
+use std::process;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::config::Config;
+use crate::day::Solver;
+use crate::input;
+use crate::outcome::{shown, Outcome};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+// the last change of every file that can change the outcomes of a day, the
+// answers file included, files that come and go count as changes
+fn snapshot(name: &str) -> Vec<(PathBuf, Option<SystemTime>)> {
+    input::day_inputs(name)
+        .iter()
+        .map(|input_file| input::path(input_file))
+        .chain(std::iter::once(input::input_dir().join("answers.txt")))
+        .map(|path| {
+            let modified = modified(&path);
+            (path, modified)
+        })
+        .collect()
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+// the source file of a day, e.g. src/day7.rs
+pub fn source_file(name: &str) -> PathBuf {
+    input::input_dir()
+        .with_file_name("src")
+        .join(format!("{}.rs", name))
+}
+
+// what changed between two runs, one line per outcome that is new or
+// differs in status or printed value from the one of the run before, an
+// error is unequal to itself but the same error is no change
+pub fn diff(previous: &[Outcome], current: &[Outcome]) -> Vec<String> {
+    current
+        .iter()
+        .filter_map(|outcome| {
+            let before = previous
+                .iter()
+                .find(|p| p.input == outcome.input && p.part == outcome.part);
+            match before {
+                Some(before)
+                    if before.status == outcome.status
+                        && shown(before.value.as_ref()) == shown(outcome.value.as_ref()) =>
+                {
+                    None
+                }
+                Some(before) => Some(format!("{} (was: {})", outcome, before.description())),
+                None => Some(outcome.to_string()),
+            }
+        })
+        .collect()
+}
+
+// builds the runner again the same way it was built, true if that worked
+fn rebuild() -> bool {
+    let mut cargo =
+        process::Command::new(std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()));
+    cargo.arg("build");
+    if !cfg!(debug_assertions) {
+        cargo.arg("--release");
+    }
+    if cfg!(feature = "count-allocations") {
+        cargo.args(["--features", "count-allocations"]);
+    }
+    let input_dir = input::input_dir();
+    match input_dir.parent() {
+        Some(root) if !root.as_os_str().is_empty() => cargo.current_dir(root),
+        _ => &mut cargo,
+    };
+    matches!(cargo.status(), Ok(status) if status.success())
+}
+
+// replaces this process with the freshly built runner and the same arguments
+fn restart() -> String {
+    let mut args = std::env::args_os();
+    let program = args.next().unwrap_or_default();
+    let mut command = process::Command::new(&program);
+    command.args(args);
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        format!(
+            "Could not restart {}: {}",
+            program.to_string_lossy(),
+            command.exec()
+        )
+    }
+    #[cfg(not(unix))]
+    match command.status() {
+        Ok(status) => process::exit(status.code().unwrap_or(1)),
+        Err(e) => format!("Could not restart {}: {}", program.to_string_lossy(), e),
+    }
+}
+
+// runs the day on all of its input files, then waits for changes and runs it
+// again, printing only what changed; this never returns unless restarting fails
+pub fn watch(
+    solver: &dyn Solver,
+    part: Option<u8>,
+    config: &Config,
+    rebuild_on_change: bool,
+) -> Result<(), String> {
+    let name = solver.name();
+    let source = source_file(&name);
+    let mut source_modified = modified(&source);
+    let mut previous: Vec<Outcome> = Vec::new();
+    loop {
+        let inputs = snapshot(&name);
+        let outcomes: Vec<Outcome> = input::day_inputs(&name)
+            .iter()
+            .flat_map(|input_file| solver.run_on(input_file, part, config))
+            .collect();
+        let changes = diff(&previous, &outcomes);
+        if changes.is_empty() {
+            println!("{}: nothing changed", name);
+        }
+        for line in changes {
+            println!("{}", line);
+        }
+        previous = outcomes;
+        println!("watching the input files of {}, stop with ctrl-c", name);
+
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            if rebuild_on_change && modified(&source) != source_modified {
+                source_modified = modified(&source);
+                println!("{} changed, rebuilding", source.display());
+                if rebuild() {
+                    return Err(restart());
+                }
+                println!("build failed, still running the old {}", name);
+            }
+            if snapshot(&name) != inputs {
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answer::Answer;
+    use crate::outcome::Status;
+
+    use super::*;
+
+    fn outcome(input: &str, part: &str, status: Status, value: &str) -> Outcome {
+        let mut outcome = Outcome::new(input, part, status);
+        outcome.value = Some(value.parse().unwrap());
+        outcome
+    }
+
+    #[test]
+    fn test_diff() {
+        let previous = vec![
+            outcome("day7_test", "1", Status::Correct, "6440"),
+            outcome("day7", "1", Status::Wrong, "251121737"),
+        ];
+        let mut current = vec![
+            outcome("day7_test", "1", Status::Correct, "6440"),
+            outcome("day7", "1", Status::Correct, "251121738"),
+            outcome("day7_test2", "1", Status::Unknown, "12"),
+            outcome("day7_test2", "2", Status::Unknown, "Err(\"no path\")"),
+        ];
+        current[1].expected = Some(Answer::from(251121738));
+        assert_eq!(
+            diff(&previous, &current),
+            vec![
+                "day7: Correct Result 251121738 (was: Expected nothing, got 251121737)",
+                "day7_test2: No answer known for part 1, got 12",
+                "day7_test2: No answer known for part 2, got Err(\"no path\")",
+            ]
+        );
+        assert!(diff(&current, &current).is_empty());
+        assert_eq!(diff(&[], &current).len(), 4);
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let files: Vec<PathBuf> = snapshot("day8").into_iter().map(|(path, _)| path).collect();
+        assert_eq!(files.len(), 4);
+        assert!(files[2].ends_with("input/day8_test2.txt"));
+        assert!(files[3].ends_with("input/answers.txt"));
+        assert!(source_file("day8").ends_with("src/day8.rs"));
+    }
+}
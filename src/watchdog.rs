@@ -0,0 +1,135 @@
+// runs parse and solve functions on worker threads with a deadline
+
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// worker threads that missed their deadline and have not finished yet
+static RUNAWAY: AtomicUsize = AtomicUsize::new(0);
+
+// how many workers that timed out still keep a core busy, timings taken
+// while there are any are skewed
+pub fn runaway() -> usize {
+    RUNAWAY.load(Ordering::SeqCst)
+}
+
+#[derive(PartialEq)]
+enum Worker {
+    Running,
+    Done,
+    Abandoned,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Failure {
+    Panicked(String),
+    Timeout(Duration),
+}
+
+// the message a panic was started with, if it is a string
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "unknown panic".to_string(),
+        },
+    }
+}
+
+// runs f on a new thread and waits for it until the deadline, if there is
+// one, returning the result and how long f took
+//
+// a thread that misses the deadline can not be stopped, it is left running
+// in the background and its result is dropped, see runaway
+pub fn run<T, F>(timeout: Option<Duration>, f: F) -> Result<(T, Duration), Failure>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+    let state = Arc::new(Mutex::new(Worker::Running));
+    let worker_state = Arc::clone(&state);
+    let worker = thread::Builder::new()
+        .name("solver".to_string())
+        .spawn(move || {
+            let start = Instant::now();
+            let result = panic::catch_unwind(AssertUnwindSafe(f));
+            let elapsed = start.elapsed();
+            let mut state = worker_state.lock().unwrap_or_else(|e| e.into_inner());
+            if *state == Worker::Abandoned {
+                RUNAWAY.fetch_sub(1, Ordering::SeqCst);
+            }
+            *state = Worker::Done;
+            drop(state);
+            // the receiver is gone when the deadline was missed
+            let _ = sender.send(result.map(|value| (value, elapsed)));
+        });
+    if let Err(e) = worker {
+        return Err(Failure::Panicked(format!(
+            "Could not start worker thread: {}",
+            e
+        )));
+    }
+
+    let received = match timeout {
+        Some(timeout) => match receiver.recv_timeout(timeout) {
+            Ok(received) => received,
+            Err(_) => {
+                let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+                if *state == Worker::Running {
+                    *state = Worker::Abandoned;
+                    RUNAWAY.fetch_add(1, Ordering::SeqCst);
+                }
+                return Err(Failure::Timeout(timeout));
+            }
+        },
+        None => receiver
+            .recv()
+            .map_err(|_| Failure::Panicked("worker thread ended without result".to_string()))?,
+    };
+    received.map_err(|payload| Failure::Panicked(panic_message(payload)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run() {
+        let (value, _) = run(None, || 6 * 7).unwrap();
+        assert_eq!(value, 42);
+    }
+
+    #[test]
+    fn test_run_panic() {
+        let result = run(None, || -> i32 { panic!("No start found") });
+        assert_eq!(
+            result.unwrap_err(),
+            Failure::Panicked("No start found".to_string())
+        );
+    }
+
+    #[test]
+    fn test_run_timeout() {
+        let (sender, receiver) = mpsc::channel::<()>();
+        let timeout = Some(Duration::from_millis(50));
+        let result = run(timeout, move || receiver.recv());
+        assert_eq!(
+            result.unwrap_err(),
+            Failure::Timeout(Duration::from_millis(50))
+        );
+        assert_eq!(runaway(), 1);
+
+        // the worker ends when the sender is gone
+        drop(sender);
+        let start = Instant::now();
+        while runaway() > 0 && start.elapsed() < Duration::from_secs(5) {
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(runaway(), 0);
+    }
+}